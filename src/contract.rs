@@ -2,15 +2,32 @@
 //! These actions are performed using *wasmd*.
 
 // #[cfg(not(feature = "library"))]
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    entry_point, from_binary, Addr, Attribute, BankMsg, Binary, BlockInfo, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    Uint128,
+};
 
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{Cw20Coin, Cw20CoinVerified, Cw20Contract, Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw721::{Cw721ReceiveMsg, Expiration};
-
-use crate::query::{query_config, query_tokens};
-use crate::state::{State, TokenInfo, CONFIG, OPERATORS, TOKENS};
+use cw_utils::{must_pay, PaymentError};
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+use crate::query::{query_config, query_tokens, resolve_token_id, token_royalty};
+use crate::state::{
+    collection_offers, offers, token_approvals, tokens, State, TokenInfo, ALLOWLIST_MINTS,
+    AUCTIONS, BIDS, CONFIG, CUSTOM_IDS, CW20_MINT_PROCEEDS, DUTCH_AUCTIONS, MINT_PROCEEDS,
+    OPERATORS, OPERATOR_OWNERS, PUBLIC_MINTS,
+};
 use crate::{
-    msg::{Approval, ExecuteMsg, InstantiateMsg, MintMsg},
+    migrations,
+    msg::{
+        AllowlistConfig, Approval, Auction, Bid, CollectionOffer, DecayCurve, DutchAuction,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, MintMsg, Offer, PauseTarget, PlatformFee,
+        PriceMode, PublicMintConfig, ReceiveMsg, Royalty,
+    },
     ContractError,
 };
 
@@ -18,6 +35,17 @@ use crate::{
 const CONTRACT_NAME: &str = "crates.io:simple-nft";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// royalties cannot exceed this percentage of the sale price
+const MAX_ROYALTY_PERCENT: u64 = 25;
+// maximum platform fee, in basis points
+const MAX_PLATFORM_FEE_BPS: u16 = 1000;
+// maximum number of operators approved for a single token
+const MAX_APPROVALS_PER_TOKEN: usize = 20;
+// maximum number of bids on a single auction, so it can always be settled
+const MAX_BIDS_PER_AUCTION: u64 = 100;
+// maximum number of items in a batch message, until the admin changes it
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 50;
+
 /// Initialise a new instance of this contract.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,15 +66,35 @@ pub fn instantiate(
             val: String::from("length of `symbol` should be greater than 1"),
         });
     }
-    // sender will be the minter for the time being
-    let minter = deps.api.addr_validate(info.sender.as_str())?;
+    // sender will be the minter, unless specified otherwise
+    let minter = match msg.minter {
+        Some(val) => deps.api.addr_validate(&val)?,
+        None => info.sender.clone(),
+    };
+
+    // sender will be the admin, unless specified otherwise
+    let admin = match msg.admin {
+        Some(val) => deps.api.addr_validate(&val)?,
+        None => info.sender.clone(),
+    };
 
     // Configure the state for storing
     let config = State {
         name: msg.name,
         symbol: msg.symbol,
-        minter,
-        num_tokens: 0u64,
+        minter: Some(minter),
+        pending_minter: None,
+        admin,
+        next_token_id: 1u64,
+        token_count: 0u64,
+        max_supply: msg.max_supply,
+        public_mint: None,
+        allowlist: None,
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        default_royalty: None,
+        next_collection_offer_id: 1u64,
+        platform_fee: None,
+        paused: vec![],
     };
     // Store
     CONFIG.save(deps.storage, &config)?;
@@ -54,6 +102,45 @@ pub fn instantiate(
     Ok(Response::default())
 }
 
+/// Upgrade an existing instance of this contract to the current version.
+/// Downgrades and instances of other contracts are rejected.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate {
+            previous_contract: stored.contract,
+        });
+    }
+
+    let previous_version: Version = stored.version.parse()?;
+    let new_version: Version = CONTRACT_VERSION.parse()?;
+    if previous_version > new_version {
+        return Err(ContractError::CannotMigrateVersion {
+            previous_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Run the migrations in order, starting from the stored version
+    if previous_version < Version::new(0, 2, 0) {
+        migrations::v0_1::migrate(deps.storage)?;
+    }
+    if previous_version < Version::new(0, 3, 0) {
+        migrations::v0_2::migrate(deps.storage)?;
+    }
+    if previous_version < Version::new(0, 4, 0) {
+        migrations::v0_3::migrate(deps.storage)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -65,31 +152,225 @@ pub fn execute(
         ExecuteMsg::TransferNft {
             recipient,
             token_id,
-        } => handle_transfer_nft(deps, env, info, recipient, token_id),
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_transfer_nft(deps, env, info, recipient, token_id)
+        }
 
         ExecuteMsg::SendNft {
             contract,
             token_id,
             msg,
-        } => handle_send_nft(deps, env, info, contract, token_id, msg),
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_send_nft(deps, env, info, contract, token_id, msg)
+        }
 
         ExecuteMsg::Approve {
             operator,
             token_id,
             expires,
-        } => handle_approve(deps, env, info, operator.as_str(), token_id, expires),
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_approve(deps, env, info, operator.as_str(), token_id, expires)
+        }
 
         ExecuteMsg::ApproveAll { operator, expires } => {
             handle_approve_all(deps, env, info, operator, expires)
         }
 
         ExecuteMsg::Revoke { operator, token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
             handle_revoke(deps, env, info, operator, token_id)
         }
 
         ExecuteMsg::RevokeAll { operator } => handle_revoke_all(deps, env, info, operator),
 
+        ExecuteMsg::PruneExpired { token_ids } => handle_prune_expired(deps, env, info, token_ids),
+
         ExecuteMsg::Mint(msg) => handle_mint(deps, env, info, msg),
+
+        ExecuteMsg::BatchMint(msgs) => handle_batch_mint(deps, env, info, msgs),
+
+        ExecuteMsg::BatchTransfer { transfers } => {
+            handle_batch_transfer(deps, env, info, transfers)
+        }
+
+        ExecuteMsg::BatchApprove {
+            operator,
+            token_ids,
+            expires,
+        } => handle_batch_approve(deps, env, info, operator, token_ids, expires),
+
+        ExecuteMsg::UpdateMaxBatchSize { max_batch_size } => {
+            handle_update_max_batch_size(deps, env, info, max_batch_size)
+        }
+
+        ExecuteMsg::PublicMint {} => {
+            handle_public_mint(deps, env, info.sender, MintPayment::Native(info.funds))
+        }
+
+        ExecuteMsg::MintAllowlisted { proof, allocation } => handle_mint_allowlisted(
+            deps,
+            env,
+            info.sender,
+            MintPayment::Native(info.funds),
+            proof,
+            allocation,
+        ),
+
+        ExecuteMsg::UpdateAllowlist { allowlist } => {
+            handle_update_allowlist(deps, env, info, allowlist)
+        }
+
+        ExecuteMsg::UpdateMerkleRoot { merkle_root } => {
+            handle_update_merkle_root(deps, env, info, merkle_root)
+        }
+
+        ExecuteMsg::UpdatePublicMint { public_mint } => {
+            handle_update_public_mint(deps, env, info, public_mint)
+        }
+
+        ExecuteMsg::WithdrawMintProceeds { recipient } => {
+            handle_withdraw_mint_proceeds(deps, env, info, recipient)
+        }
+
+        ExecuteMsg::ProposeMinter { new_minter } => {
+            handle_propose_minter(deps, env, info, new_minter)
+        }
+
+        ExecuteMsg::AcceptMinter {} => handle_accept_minter(deps, env, info),
+
+        ExecuteMsg::RenounceMinter {} => handle_renounce_minter(deps, env, info),
+
+        ExecuteMsg::StartAuction {
+            token_id,
+            reserve_price,
+            min_increment,
+            ends_at,
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_start_auction(
+                deps,
+                env,
+                info,
+                token_id,
+                reserve_price,
+                min_increment,
+                ends_at,
+            )
+        }
+
+        ExecuteMsg::PlaceBid { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_place_bid(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::CancelAuction { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_cancel_auction(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::SettleAuction { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_settle_auction(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::StartDutchAuction {
+            token_id,
+            start_price,
+            floor_price,
+            start_time,
+            end_time,
+            curve,
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            let auction = DutchAuction {
+                token_id: String::new(),
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+                curve,
+            };
+            handle_start_dutch_auction(deps, env, info, token_id, auction)
+        }
+
+        ExecuteMsg::MakeOffer { token_id, expires } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_make_offer(deps, env, info, token_id, expires)
+        }
+
+        ExecuteMsg::CancelOffer { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_cancel_offer(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::AcceptOffer { token_id, bidder } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_accept_offer(deps, env, info, token_id, bidder)
+        }
+
+        ExecuteMsg::MakeCollectionOffer {
+            price,
+            quantity,
+            expires,
+        } => handle_make_collection_offer(deps, env, info, price, quantity, expires),
+
+        ExecuteMsg::CancelCollectionOffer { offer_id } => {
+            handle_cancel_collection_offer(deps, env, info, offer_id)
+        }
+
+        ExecuteMsg::AcceptCollectionOffer { token_id, offer_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_accept_collection_offer(deps, env, info, token_id, offer_id)
+        }
+
+        ExecuteMsg::Pause { targets } => handle_pause(deps, env, info, targets),
+
+        ExecuteMsg::Unpause { targets } => handle_unpause(deps, env, info, targets),
+
+        ExecuteMsg::UpdatePlatformFee { platform_fee } => {
+            handle_update_platform_fee(deps, env, info, platform_fee)
+        }
+
+        ExecuteMsg::SetRoyalty { token_id, royalty } => {
+            let token_id = token_id
+                .map(|val| resolve_token_id(deps.as_ref(), &val))
+                .transpose()?;
+            handle_set_royalty(deps, env, info, token_id, royalty)
+        }
+
+        ExecuteMsg::Burn { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_burn(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::Buy { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_buy(deps, env, info, token_id)
+        }
+
+        ExecuteMsg::SetPrice {
+            token_id,
+            price,
+            price_mode,
+        } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_set_price(deps, env, info, token_id, price, price_mode)
+        }
+
+        ExecuteMsg::SetCw20Price { token_id, price } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_set_cw20_price(deps, env, info, token_id, price)
+        }
+
+        ExecuteMsg::Receive(msg) => handle_receive(deps, env, info, msg),
+
+        ExecuteMsg::Delist { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_delist(deps, env, info, token_id)
+        }
     }
 }
 
@@ -97,20 +378,17 @@ pub fn authorized_to_send(
     deps: Deps,
     env: &Env,
     info: &MessageInfo,
-    token_id: u64,
+    token: &TokenInfo,
 ) -> Result<(), ContractError> {
-    let token = query_tokens(deps, token_id)?;
     if token.owner == info.sender {
         return Ok(());
     };
 
-    let token_appr = token
-        .approvals
-        .iter()
-        .any(|val| val.operator == info.sender && !val.expires.is_expired(&env.block));
-
-    if token_appr {
-        return Ok(());
+    let token_appr = token_approvals().may_load(deps.storage, (token.token_id, &info.sender))?;
+    if let Some(val) = token_appr {
+        if !val.expires.is_expired(&env.block) {
+            return Ok(());
+        }
     }
 
     let super_appr = OPERATORS.may_load(deps.storage, (&token.owner, &info.sender))?;
@@ -126,9 +404,8 @@ fn authorized_to_approve(
     deps: Deps,
     env: &Env,
     info: &MessageInfo,
-    token_id: u64,
+    token: &TokenInfo,
 ) -> Result<(), ContractError> {
-    let token = query_tokens(deps, token_id)?;
     // Either token owner can approve
     if token.owner == info.sender {
         return Ok(());
@@ -152,20 +429,91 @@ pub fn handle_transfer_nft(
     recipient: String,
     token_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut requested_token = TOKENS.load(deps.storage, token_id)?;
-
-    authorized_to_send(deps.as_ref(), &env, &info, token_id)?;
-
-    requested_token.owner = deps.api.addr_validate(&recipient)?;
-    requested_token.approvals = vec![];
+    ensure_not_paused(deps.as_ref(), PauseTarget::Transfers)?;
 
-    TOKENS.save(deps.storage, token_id, &requested_token)?;
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+    authorized_transfer(deps, &env, &info, &mut token, &recipient)?;
 
     Ok(Response::new()
         .add_attribute("action", "transfer_nft")
         .add_attribute("from", info.sender)
         .add_attribute("to", recipient)
-        .add_attribute("token_id", token_id.to_string()))
+        .add_attribute("token_id", token.external_id()))
+}
+
+/// Transfer `token` to `recipient` on behalf of the sender, once it has been
+/// checked that the sender may move it. Shared by `TransferNft` and `SendNft`.
+fn authorized_transfer(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token: &mut TokenInfo,
+    recipient: &str,
+) -> Result<(), ContractError> {
+    authorized_to_send(deps.as_ref(), env, info, token)?;
+    ensure_transferable(token)?;
+    ensure_not_in_auction(deps.as_ref(), token.token_id)?;
+
+    let recipient_addr = deps.api.addr_validate(recipient)?;
+    transfer_token(deps.storage, token, recipient_addr)?;
+    Ok(())
+}
+
+/// Save `token`, dropping its expired approvals on the way.
+fn save_token(storage: &mut dyn Storage, block: &BlockInfo, token: &TokenInfo) -> StdResult<()> {
+    prune_approvals(storage, block, token.token_id)?;
+    tokens().save(storage, token.token_id, token)
+}
+
+/// Remove the expired approvals of a token, returning how many were removed.
+fn prune_approvals(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    token_id: u64,
+) -> StdResult<usize> {
+    let expired: StdResult<Vec<_>> = token_approvals()
+        .prefix(token_id)
+        .range(storage, None, None, Order::Ascending)
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |(_, appr)| appr.expires.is_expired(block))
+        })
+        .map(|res| res.map(|(operator, _)| operator))
+        .collect();
+
+    let expired = expired?;
+    for operator in &expired {
+        token_approvals().remove(storage, (token_id, operator))?;
+    }
+    Ok(expired.len())
+}
+
+/// Remove every approval of a token.
+fn clear_approvals(storage: &mut dyn Storage, token_id: u64) -> StdResult<()> {
+    let operators: StdResult<Vec<_>> = token_approvals()
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect();
+
+    for operator in operators? {
+        token_approvals().remove(storage, (token_id, &operator))?;
+    }
+    Ok(())
+}
+
+/// Change the owner of `token` to `recipient`, clear all the approvals
+/// granted by the previous owner and take the token off sale.
+fn transfer_token(
+    storage: &mut dyn Storage,
+    token: &mut TokenInfo,
+    recipient: Addr,
+) -> StdResult<()> {
+    token.owner = recipient;
+    token.listed = false;
+    clear_approvals(storage, token.token_id)?;
+    DUTCH_AUCTIONS.remove(storage, token.token_id);
+
+    tokens().save(storage, token.token_id, token)
 }
 
 fn handle_send_nft(
@@ -176,11 +524,14 @@ fn handle_send_nft(
     token_id: u64,
     msg: Binary,
 ) -> Result<Response, ContractError> {
-    handle_transfer_nft(deps, env, info.clone(), contract.clone(), token_id)?;
+    ensure_not_paused(deps.as_ref(), PauseTarget::Transfers)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+    authorized_transfer(deps, &env, &info, &mut token, &contract)?;
 
     let msg = Cw721ReceiveMsg {
         sender: info.sender.to_string(),
-        token_id: token_id.to_string(),
+        token_id: token.external_id(),
         msg,
     };
 
@@ -189,7 +540,7 @@ fn handle_send_nft(
         .add_attribute("action", "send_nft")
         .add_attribute("from", info.sender)
         .add_attribute("to", contract)
-        .add_attribute("token_id", token_id.to_string()))
+        .add_attribute("token_id", token.external_id()))
 }
 
 pub fn handle_approve(
@@ -200,10 +551,14 @@ pub fn handle_approve(
     token_id: u64,
     expires: Option<Expiration>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Approvals)?;
+
     // Load the token with given token id
-    let mut token = query_tokens(deps.as_ref(), token_id)?;
+    let token = query_tokens(deps.as_ref(), token_id)?;
 
-    authorized_to_approve(deps.as_ref(), &env, &info, token_id)?;
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
 
     let appr = Approval {
         operator: deps.api.addr_validate(operator)?,
@@ -216,16 +571,28 @@ pub fn handle_approve(
     if appr.expires.is_expired(&env.block) {
         return Err(ContractError::Expired);
     }
-    // Apply approval to the token
-    token.approvals.push(appr);
 
-    TOKENS.save(deps.storage, token_id, &token)?;
+    // Approving an operator again only updates the expiry of its approval
+    prune_approvals(deps.storage, &env.block, token_id)?;
+    let key = (token_id, &appr.operator);
+    if token_approvals().may_load(deps.storage, key)?.is_none() {
+        let count = token_approvals()
+            .prefix(token_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count();
+        if count >= MAX_APPROVALS_PER_TOKEN {
+            return Err(ContractError::TooManyApprovals {
+                max: MAX_APPROVALS_PER_TOKEN as u32,
+            });
+        }
+    }
+    token_approvals().save(deps.storage, key, &appr)?;
 
     Ok(Response::new()
         .add_attribute("action", "approve")
         .add_attribute("from", info.sender)
         .add_attribute("approved", operator)
-        .add_attribute("token_id", token_id.to_string()))
+        .add_attribute("token_id", token.external_id()))
 }
 
 fn handle_approve_all(
@@ -235,6 +602,8 @@ fn handle_approve_all(
     operator: String,
     expires: Option<Expiration>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Approvals)?;
+
     let operator_addr = deps.api.addr_validate(&operator[..])?;
     let expires = match expires {
         Some(val) => val,
@@ -254,6 +623,7 @@ fn handle_approve_all(
 
     // Save the new/updated details
     OPERATORS.save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+    OPERATOR_OWNERS.save(deps.storage, (&operator_addr, &info.sender), &expires)?;
 
     Ok(Response::new()
         .add_attribute("action", "approve_all")
@@ -268,25 +638,41 @@ pub fn handle_revoke(
     operator: String,
     token_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut token = query_tokens(deps.as_ref(), token_id)?;
+    let token = query_tokens(deps.as_ref(), token_id)?;
 
-    authorized_to_approve(deps.as_ref(), &env, &info, token_id)?;
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
 
     let operator_addr = deps.api.addr_validate(operator.as_str())?;
-    let revoked: Vec<Approval> = token
-        .approvals
-        .into_iter()
-        .filter(|val| val.operator != operator_addr)
-        .collect();
-
-    token.approvals = revoked;
-    TOKENS.save(deps.storage, token_id, &token)?;
+    prune_approvals(deps.storage, &env.block, token_id)?;
+    token_approvals().remove(deps.storage, (token_id, &operator_addr))?;
 
     Ok(Response::new()
         .add_attribute("action", "revoke")
         .add_attribute("from", info.sender)
         .add_attribute("revoked", operator)
-        .add_attribute("token_id", token_id.to_string()))
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_prune_expired(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    check_batch_size(deps.as_ref(), token_ids.len())?;
+
+    let mut pruned = 0;
+    for token_id in token_ids {
+        let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+        query_tokens(deps.as_ref(), token_id)?;
+
+        pruned += prune_approvals(deps.storage, &env.block, token_id)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_expired")
+        .add_attribute("from", info.sender)
+        .add_attribute("pruned", pruned.to_string()))
 }
 
 pub fn handle_revoke_all(
@@ -299,6 +685,7 @@ pub fn handle_revoke_all(
 
     if OPERATORS.has(deps.storage, (&info.sender, &operator_addr)) {
         OPERATORS.remove(deps.storage, (&info.sender, &operator_addr));
+        OPERATOR_OWNERS.remove(deps.storage, (&operator_addr, &info.sender));
     } else {
         return Err(ContractError::ApprovalNotFound { operator });
     }
@@ -315,581 +702,5889 @@ pub fn handle_mint(
     info: MessageInfo,
     msg: MintMsg,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Minting)?;
+
     // Load current contract state
     let mut config = query_config(deps.as_ref())?;
 
     // sender and minter address should be same
-    if info.sender != config.minter {
+    if config.minter.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized);
     }
 
-    // price of the new NFT cannot be zero
-    if msg.price.is_empty() {
-        return Err(ContractError::CustomError {
-            val: String::from("Token price cannot be empty"),
-        });
-    } else {
-        // let res: Vec<&Coin> = msg
-        //     .price
-        //     .iter()
-        //     .filter(|val| val.amount.is_zero())
-        //     .collect();
-        for val in msg.price.iter() {
-            if val.amount.is_zero() {
-                return Err(ContractError::CustomError {
-                    val: String::from("Token price cannot be zero"),
-                });
-            }
-        }
+    // reserved mints count towards the max supply as well
+    check_supply(&config)?;
+
+    let cw20_price = msg
+        .cw20_price
+        .map(|val| validate_cw20_price(deps.as_ref(), val))
+        .transpose()?;
+
+    // price of the new NFT cannot be empty or zero, unless it is priced
+    // in a CW20 token instead
+    if cw20_price.is_none() || !msg.price.is_empty() {
+        validate_price(&msg.price)?;
+    }
+
+    if let Some(royalty) = &msg.royalty {
+        validate_royalty(deps.as_ref(), royalty)?;
+    }
+
+    // Ids are never reused, even if the token has been burned
+    let token_id = config.next_token_id;
+
+    // Custom ids cannot collide with numeric ids or other custom ids
+    if let Some(custom_id) = &msg.token_id {
+        validate_custom_id(deps.as_ref(), custom_id)?;
+        CUSTOM_IDS.save(deps.storage, custom_id, &token_id)?;
     }
 
-    // Increase the current amount of tokens issued
-    let num_tokens = config.num_tokens + 1;
+    // Soulbound tokens are never for sale
+    let transferable = msg.transferable.unwrap_or(true);
+
     // Create a new token
     let token = TokenInfo {
         owner: deps.api.addr_validate(&msg.owner)?,
-        approvals: vec![],
         token_uri: msg.token_uri,
+        extension: msg.extension,
+        royalty: msg.royalty,
         base_price: msg.price,
-        token_id: num_tokens,
+        price_mode: msg.price_mode.unwrap_or_default(),
+        cw20_price,
+        listed: transferable,
+        transferable,
+        token_id,
+        custom_id: msg.token_id,
     };
     // Save the new token to storage
-    TOKENS.save(deps.storage, num_tokens, &token)?;
+    tokens().save(deps.storage, token_id, &token)?;
 
     // Increase the number of tokens issued in state
-    config.num_tokens = num_tokens;
+    config.next_token_id += 1;
+    config.token_count += 1;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
         .add_attribute("action", "mint")
         .add_attribute("from", info.sender)
         .add_attribute("owner", msg.owner)
-        .add_attribute("token_id", num_tokens.to_string()))
+        .add_attribute("token_id", token.external_id()))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, Addr, StdError};
-    use cw721::Expiration;
+pub fn handle_batch_mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<MintMsg>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Minting)?;
 
-    const DENOM: &str = "ubit";
+    check_batch_size(deps.as_ref(), msgs.len())?;
 
-    fn init_msg(name: String, symbol: String) -> InstantiateMsg {
-        InstantiateMsg { name, symbol }
+    let mut res = Response::new()
+        .add_attribute("action", "batch_mint")
+        .add_attribute("from", info.sender.clone())
+        .add_attribute("count", msgs.len().to_string());
+    for msg in msgs {
+        let sub_res = handle_mint(deps.branch(), env.clone(), info.clone(), msg)?;
+        res = res.add_attributes(batch_item_attributes(sub_res));
     }
+    Ok(res)
+}
 
-    fn mint_msg(owner: String) -> MintMsg {
-        MintMsg {
-            owner,
-            token_uri: None,
-            price: coins(1000, &DENOM.to_string()),
-        }
+pub fn handle_batch_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfers: Vec<(String, String)>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Transfers)?;
+
+    check_batch_size(deps.as_ref(), transfers.len())?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "batch_transfer")
+        .add_attribute("from", info.sender.clone())
+        .add_attribute("count", transfers.len().to_string());
+    for (recipient, token_id) in transfers {
+        let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+        let sub_res = handle_transfer_nft(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            recipient,
+            token_id,
+        )?;
+        res = res.add_attributes(batch_item_attributes(sub_res));
     }
+    Ok(res)
+}
 
-    #[test]
-    fn proper_initialization() {
-        // Create mock dependencies and environment
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+pub fn handle_batch_approve(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    token_ids: Vec<String>,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Approvals)?;
+
+    check_batch_size(deps.as_ref(), token_ids.len())?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "batch_approve")
+        .add_attribute("from", info.sender.clone())
+        .add_attribute("approved", operator.clone())
+        .add_attribute("count", token_ids.len().to_string());
+    for token_id in token_ids {
+        let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+        let sub_res = handle_approve(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            &operator,
+            token_id,
+            expires,
+        )?;
+        res = res.add_attributes(batch_item_attributes(sub_res));
+    }
+    Ok(res)
+}
 
-        // Successful instantiation
-        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+pub fn handle_update_max_batch_size(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_batch_size: u32,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
 
-        let stored_state = query_config(deps.as_ref()).unwrap();
-        assert_eq!(stored_state.name, "TestNFT");
-        assert_eq!(stored_state.symbol, "NFT");
-        assert_eq!(stored_state.num_tokens, 0u64);
-        assert_eq!(stored_state.minter, Addr::unchecked("creator"));
+    // only the admin can change the batch size
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
 
-        // Following tests are to check correct error when no value is given
-        // to either of the fields in InstantiateMsg.
-        // * Missing name
-        let msg = init_msg(String::new(), "NFT".to_string());
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    if max_batch_size == 0 {
+        return Err(ContractError::CustomError {
+            val: String::from("Max batch size cannot be zero"),
+        });
+    }
+    config.max_batch_size = max_batch_size;
+    CONFIG.save(deps.storage, &config)?;
 
-        match res {
-            ContractError::CustomError { .. } => {}
-            e => panic!("{:?}", e),
-        }
+    Ok(Response::new()
+        .add_attribute("action", "update_max_batch_size")
+        .add_attribute("from", info.sender)
+        .add_attribute("max_batch_size", max_batch_size.to_string()))
+}
 
-        // * Missing symbol
-        let msg = init_msg(String::from("TestNFT"), String::new());
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+/// Batches cannot be empty or have more items than the configured maximum.
+fn check_batch_size(deps: Deps, len: usize) -> Result<(), ContractError> {
+    if len == 0 {
+        return Err(ContractError::CustomError {
+            val: String::from("Batch cannot be empty"),
+        });
+    }
 
-        match res {
-            ContractError::CustomError { .. } => {}
-            e => panic!("{:?}", e),
-        }
+    let max_batch_size = query_config(deps)?.max_batch_size;
+    if len > max_batch_size as usize {
+        return Err(ContractError::BatchTooLarge { max_batch_size });
+    }
+    Ok(())
+}
 
-        // * Missing name and symbol
-        let msg = init_msg(String::from(""), String::from(""));
-        let res = instantiate(deps.as_mut(), env, info, msg).unwrap_err();
+/// Attributes of a single item of a batch, without the `action` and `from`
+/// attributes already present once on the batch event.
+fn batch_item_attributes(res: Response) -> impl Iterator<Item = Attribute> {
+    res.attributes
+        .into_iter()
+        .filter(|attr| attr.key != "action" && attr.key != "from")
+}
 
+/// Payment sent for a public or allowlist mint, either along with the
+/// message or through a CW20 `Send`.
+pub enum MintPayment {
+    Native(Vec<Coin>),
+    Cw20(Cw20CoinVerified),
+}
+
+pub fn handle_public_mint(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    payment: MintPayment,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Minting)?;
+
+    let config = query_config(deps.as_ref())?;
+
+    // renouncing the minter closes the public mint too
+    if config.minter.is_none() {
+        return Err(ContractError::PublicMintClosed);
+    }
+
+    let public_mint = match config.public_mint.clone() {
+        Some(val) if val.start_time <= env.block.time && env.block.time < val.end_time => val,
+        _ => return Err(ContractError::PublicMintClosed),
+    };
+
+    let minted = PUBLIC_MINTS
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+    if minted >= public_mint.per_address_limit {
+        return Err(ContractError::MintLimitReached {
+            limit: public_mint.per_address_limit,
+        });
+    }
+
+    check_supply(&config)?;
+    let cw20_price = pay_mint_price(
+        deps.storage,
+        &public_mint.price,
+        public_mint.cw20_price,
+        payment,
+    )?;
+    let token_id = mint_paid_token(
+        deps.storage,
+        config,
+        &sender,
+        public_mint.price,
+        cw20_price,
+        public_mint.base_token_uri,
+    )?;
+    PUBLIC_MINTS.save(deps.storage, &sender, &(minted + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "public_mint")
+        .add_attribute("from", sender)
+        .add_attribute("token_id", token_id.to_string()))
+}
+
+pub fn handle_mint_allowlisted(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    payment: MintPayment,
+    proof: Vec<String>,
+    allocation: u32,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Minting)?;
+
+    let config = query_config(deps.as_ref())?;
+
+    // renouncing the minter closes the allowlist mint too
+    if config.minter.is_none() {
+        return Err(ContractError::AllowlistMintClosed);
+    }
+
+    let allowlist = match config.allowlist.clone() {
+        Some(val) if val.start_time <= env.block.time && env.block.time < val.end_time => val,
+        _ => return Err(ContractError::AllowlistMintClosed),
+    };
+
+    verify_allowlist_proof(&allowlist.merkle_root, &sender, allocation, &proof)?;
+
+    // the allocation committed to in the root is capped by the phase limit
+    let limit = allocation.min(allowlist.per_address_limit);
+    let minted = ALLOWLIST_MINTS
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+    if minted >= limit {
+        return Err(ContractError::MintLimitReached { limit });
+    }
+
+    check_supply(&config)?;
+    let cw20_price = pay_mint_price(
+        deps.storage,
+        &allowlist.price,
+        allowlist.cw20_price,
+        payment,
+    )?;
+    let token_id = mint_paid_token(
+        deps.storage,
+        config,
+        &sender,
+        allowlist.price,
+        cw20_price,
+        allowlist.base_token_uri,
+    )?;
+    ALLOWLIST_MINTS.save(deps.storage, &sender, &(minted + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint_allowlisted")
+        .add_attribute("from", sender)
+        .add_attribute("token_id", token_id.to_string()))
+}
+
+/// Check that `payment` pays the mint price of a phase, in the CW20 token
+/// if `cw20_price` is set or in native coins otherwise, and return the CW20
+/// price paid. The proceeds stay in the contract until the admin withdraws
+/// them.
+fn pay_mint_price(
+    storage: &mut dyn Storage,
+    price: &[Coin],
+    cw20_price: Option<Cw20Coin>,
+    payment: MintPayment,
+) -> Result<Option<Cw20CoinVerified>, ContractError> {
+    match (cw20_price, payment) {
+        (None, MintPayment::Native(funds)) => {
+            let paid = check_funds(price, PriceMode::AllOf, &funds)?;
+            for coin in paid {
+                MINT_PROCEEDS.update(storage, &coin.denom, |val| -> StdResult<_> {
+                    Ok(val.unwrap_or_default() + coin.amount)
+                })?;
+            }
+            Ok(None)
+        }
+        (Some(price), MintPayment::Cw20(paid)) => {
+            // address has been validated when the phase was configured
+            let price = Cw20CoinVerified {
+                address: Addr::unchecked(price.address),
+                amount: price.amount,
+            };
+            if paid != price {
+                return Err(ContractError::InvalidCw20Amount {
+                    val: price,
+                    funds: paid,
+                });
+            }
+            CW20_MINT_PROCEEDS.update(storage, &paid.address, |val| -> StdResult<_> {
+                Ok(val.unwrap_or_default() + paid.amount)
+            })?;
+            Ok(Some(price))
+        }
+        (Some(_), MintPayment::Native(_)) => Err(ContractError::CustomError {
+            val: String::from("Mint is priced in a CW20 token"),
+        }),
+        (None, MintPayment::Cw20(_)) => Err(ContractError::CustomError {
+            val: String::from("Mint is not priced in a CW20 token"),
+        }),
+    }
+}
+
+/// Mint a token to `owner` once the supply has been checked and the mint
+/// price paid, for the public and allowlist phases. Like any other newly
+/// minted token, it is listed at the price it has been minted for.
+fn mint_paid_token(
+    storage: &mut dyn Storage,
+    mut config: State,
+    owner: &Addr,
+    price: Vec<Coin>,
+    cw20_price: Option<Cw20CoinVerified>,
+    base_token_uri: Option<String>,
+) -> Result<u64, ContractError> {
+    let token_id = config.next_token_id;
+    let token = TokenInfo {
+        owner: owner.clone(),
+        token_uri: base_token_uri.map(|uri| format!("{}{}", uri, token_id)),
+        extension: None,
+        royalty: None,
+        base_price: price,
+        price_mode: PriceMode::AllOf,
+        cw20_price,
+        listed: true,
+        transferable: true,
+        token_id,
+        custom_id: None,
+    };
+    tokens().save(storage, token_id, &token)?;
+
+    config.next_token_id += 1;
+    config.token_count += 1;
+    CONFIG.save(storage, &config)?;
+    Ok(token_id)
+}
+
+/// Check that `proof` leads from the leaf of (`address`, `allocation`) to
+/// the hex encoded sha256 `merkle_root`. Pairs of hashes are sorted before
+/// being hashed together, so the proof does not need to give the position
+/// of each sibling.
+fn verify_allowlist_proof(
+    merkle_root: &str,
+    address: &Addr,
+    allocation: u32,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let leaf: [u8; 32] = Sha256::digest(format!("{}:{}", address, allocation).as_bytes()).into();
+
+    let hash = proof.iter().try_fold(leaf, |hash, sibling| {
+        let mut sibling_hash = [0u8; 32];
+        hex::decode_to_slice(sibling, &mut sibling_hash)
+            .map_err(|_| ContractError::InvalidProof)?;
+
+        let mut pair = [hash, sibling_hash];
+        pair.sort_unstable();
+        Ok::<_, ContractError>(Sha256::digest(pair.concat()).into())
+    })?;
+
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(merkle_root, &mut root).map_err(|_| ContractError::InvalidProof)?;
+    if hash != root {
+        return Err(ContractError::InvalidProof);
+    }
+    Ok(())
+}
+
+pub fn handle_update_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    mut allowlist: Option<AllowlistConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can configure the allowlist
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if let Some(val) = &mut allowlist {
+        // minting cannot be reopened once the minter has been renounced
+        if config.minter.is_none() {
+            return Err(ContractError::AllowlistMintClosed);
+        }
+
+        validate_merkle_root(&val.merkle_root)?;
+        validate_mint_price(deps.as_ref(), &val.price, &mut val.cw20_price)?;
+
+        if val.end_time <= val.start_time {
+            return Err(ContractError::CustomError {
+                val: String::from("Allowlist mint has to end after it starts"),
+            });
+        }
+
+        if val.per_address_limit == 0 {
+            return Err(ContractError::CustomError {
+                val: String::from("Per address limit cannot be zero"),
+            });
+        }
+    }
+    config.allowlist = allowlist;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_allowlist")
+        .add_attribute("from", info.sender))
+}
+
+pub fn handle_update_merkle_root(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can swap the root
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    validate_merkle_root(&merkle_root)?;
+    let allowlist = config
+        .allowlist
+        .as_mut()
+        .ok_or_else(|| ContractError::CustomError {
+            val: String::from("No allowlist has been configured"),
+        })?;
+    allowlist.merkle_root = merkle_root.clone();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_merkle_root")
+        .add_attribute("from", info.sender)
+        .add_attribute("merkle_root", merkle_root))
+}
+
+/// Merkle root has to be a hex encoded sha256 hash.
+fn validate_merkle_root(merkle_root: &str) -> Result<(), ContractError> {
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(merkle_root, &mut root).map_err(|_| ContractError::CustomError {
+        val: String::from("Merkle root has to be a hex encoded sha256 hash"),
+    })?;
+    Ok(())
+}
+
+pub fn handle_update_public_mint(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    mut public_mint: Option<PublicMintConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can configure the public mint
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if let Some(val) = &mut public_mint {
+        // minting cannot be reopened once the minter has been renounced
+        if config.minter.is_none() {
+            return Err(ContractError::PublicMintClosed);
+        }
+
+        validate_mint_price(deps.as_ref(), &val.price, &mut val.cw20_price)?;
+
+        if val.end_time <= val.start_time {
+            return Err(ContractError::CustomError {
+                val: String::from("Public mint has to end after it starts"),
+            });
+        }
+
+        if val.per_address_limit == 0 {
+            return Err(ContractError::CustomError {
+                val: String::from("Per address limit cannot be zero"),
+            });
+        }
+    }
+    config.public_mint = public_mint;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_public_mint")
+        .add_attribute("from", info.sender))
+}
+
+pub fn handle_withdraw_mint_proceeds(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = query_config(deps.as_ref())?;
+
+    // only the admin can withdraw
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let recipient = match recipient {
+        Some(val) => deps.api.addr_validate(&val)?,
+        None => config.admin,
+    };
+
+    let proceeds: StdResult<Vec<_>> = MINT_PROCEEDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+    let mut amount = vec![];
+    for (denom, val) in proceeds? {
+        MINT_PROCEEDS.remove(deps.storage, &denom);
+        amount.push(Coin::new(val.u128(), denom));
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if !amount.is_empty() {
+        msgs.push(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount,
+            }
+            .into(),
+        );
+    }
+
+    let cw20_proceeds: StdResult<Vec<_>> = CW20_MINT_PROCEEDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+    for (address, val) in cw20_proceeds? {
+        CW20_MINT_PROCEEDS.remove(deps.storage, &address);
+        msgs.push(Cw20Contract(address).call(Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: val,
+        })?);
+    }
+
+    if msgs.is_empty() {
+        return Err(ContractError::CustomError {
+            val: String::from("No proceeds to withdraw"),
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "withdraw_mint_proceeds")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", recipient))
+}
+
+/// No token can be minted once `max_supply` tokens have been minted.
+/// Burned tokens still count, as their ids are never reused.
+fn check_supply(config: &State) -> Result<(), ContractError> {
+    if let Some(max_supply) = config.max_supply {
+        if config.next_token_id > max_supply {
+            return Err(ContractError::MaxSupplyReached { max_supply });
+        }
+    }
+    Ok(())
+}
+
+/// Custom token ids cannot be empty, numeric or already in use.
+fn validate_custom_id(deps: Deps, token_id: &str) -> Result<(), ContractError> {
+    if token_id.is_empty() {
+        return Err(ContractError::CustomError {
+            val: String::from("Custom token_id cannot be empty"),
+        });
+    }
+
+    if token_id.parse::<u64>().is_ok() {
+        return Err(ContractError::CustomError {
+            val: String::from("Custom token_id cannot be numeric"),
+        });
+    }
+
+    if CUSTOM_IDS.has(deps.storage, token_id) {
+        return Err(ContractError::Claimed {
+            token_id: token_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn handle_propose_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    new_minter: String,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the current minter can hand over the role
+    if config.minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    config.pending_minter = Some(deps.api.addr_validate(&new_minter)?);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_minter")
+        .add_attribute("from", info.sender)
+        .add_attribute("pending_minter", new_minter))
+}
+
+pub fn handle_accept_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the proposed minter can accept the role
+    if config.pending_minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    config.minter = config.pending_minter.take();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_minter")
+        .add_attribute("minter", info.sender))
+}
+
+pub fn handle_renounce_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    if config.minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    // Without a minter, no more tokens can ever be minted
+    config.minter = None;
+    config.pending_minter = None;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_minter")
+        .add_attribute("from", info.sender))
+}
+
+pub fn handle_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Transfers)?;
+
+    let token = query_tokens(deps.as_ref(), token_id)?;
+    let mut config = query_config(deps.as_ref())?;
+
+    // Soulbound tokens can be burned by their owner, or revoked by the minter
+    if token.transferable {
+        authorized_to_send(deps.as_ref(), &env, &info, &token)?;
+    } else if token.owner != info.sender && config.minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    tokens().remove(deps.storage, token_id)?;
+    clear_approvals(deps.storage, token_id)?;
+    DUTCH_AUCTIONS.remove(deps.storage, token_id);
+
+    // Decrease the number of live tokens in state
+    config.token_count -= 1;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("from", info.sender)
+        .add_attribute("owner", &token.owner)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    // owner cannot buy their own token
+    if token.owner == info.sender {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot buy a token you already own"),
+        });
+    }
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    let mut res = Response::new();
+    // Dutch auctions take precedence over the fixed price
+    let price = match DUTCH_AUCTIONS.may_load(deps.storage, token_id)? {
+        Some(auction) => {
+            if env.block.time < auction.start_time {
+                return Err(ContractError::NotListed { token_id });
+            }
+
+            let price = auction.price_at(env.block.time);
+            let paid = must_pay(&info, &price.denom)?;
+            if paid < price.amount {
+                return Err(ContractError::InvalidAmount {
+                    val: price,
+                    funds: Coin::new(paid.u128(), auction.start_price.denom),
+                });
+            }
+
+            // Refund whatever was paid above the current price
+            let refund = paid - price.amount;
+            if !refund.is_zero() {
+                res = res.add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![Coin::new(refund.u128(), price.denom.clone())],
+                });
+            }
+            vec![price]
+        }
+        None => {
+            if !token.listed {
+                return Err(ContractError::NotListed { token_id });
+            }
+            if token.cw20_price.is_some() {
+                return Err(ContractError::CustomError {
+                    val: String::from("Token is priced in a CW20 token"),
+                });
+            }
+            check_funds(&token.base_price, token.price_mode, &info.funds)?
+        }
+    };
+
+    let seller = token.owner.clone();
+    transfer_token(deps.storage, &mut token, info.sender.clone())?;
+
+    // Pay the royalty and the previous owner
+    let payments = sale_payouts(deps.as_ref(), &token, &seller, &price)?;
+
+    Ok(res
+        .add_messages(payments)
+        .add_attribute("action", "buy")
+        .add_attribute("from", seller)
+        .add_attribute("to", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The sender of the message is the CW20 contract, the tokens come from
+    // the account given in the wrapper
+    let payment = Cw20CoinVerified {
+        address: info.sender,
+        amount: wrapper.amount,
+    };
+    let buyer = deps.api.addr_validate(&wrapper.sender)?;
+
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::Buy { token_id } => {
+            let token_id = resolve_token_id(deps.as_ref(), &token_id)?;
+            handle_cw20_buy(deps, env, buyer, payment, token_id)
+        }
+        ReceiveMsg::PublicMint {} => {
+            handle_public_mint(deps, env, buyer, MintPayment::Cw20(payment))
+        }
+        ReceiveMsg::MintAllowlisted { proof, allocation } => handle_mint_allowlisted(
+            deps,
+            env,
+            buyer,
+            MintPayment::Cw20(payment),
+            proof,
+            allocation,
+        ),
+    }
+}
+
+pub fn handle_cw20_buy(
+    deps: DepsMut,
+    _env: Env,
+    buyer: Addr,
+    payment: Cw20CoinVerified,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    // owner cannot buy their own token
+    if token.owner == buyer {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot buy a token you already own"),
+        });
+    }
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    if !token.listed {
+        return Err(ContractError::NotListed { token_id });
+    }
+    let price = token
+        .cw20_price
+        .clone()
+        .ok_or_else(|| ContractError::CustomError {
+            val: String::from("Token is not priced in a CW20 token"),
+        })?;
+    if payment != price {
+        return Err(ContractError::InvalidCw20Amount {
+            val: price,
+            funds: payment,
+        });
+    }
+
+    let seller = token.owner.clone();
+    transfer_token(deps.storage, &mut token, buyer.clone())?;
+
+    // Pay the royalty and the previous owner
+    let payments = cw20_sale_payouts(deps.as_ref(), &token, &seller, &price)?;
+
+    Ok(Response::new()
+        .add_messages(payments)
+        .add_attribute("action", "buy")
+        .add_attribute("from", seller)
+        .add_attribute("to", buyer)
+        .add_attribute("token_id", token.external_id()))
+}
+
+/// Split the proceeds of a sale of `token` between the platform fee
+/// recipients, the royalty recipient and the seller. The fee and the
+/// royalty are both taken from the full price.
+fn sale_payouts(
+    deps: Deps,
+    token: &TokenInfo,
+    seller: &Addr,
+    price: &[Coin],
+) -> StdResult<Vec<BankMsg>> {
+    let config = query_config(deps)?;
+    let royalty = token_royalty(deps, token)?;
+    let fee_recipients = match &config.platform_fee {
+        Some(val) => val.recipients.clone(),
+        None => vec![],
+    };
+
+    let mut fees: Vec<Vec<Coin>> = vec![vec![]; fee_recipients.len()];
+    let mut royalties = vec![];
+    let mut proceeds = vec![];
+    for coin in price.iter() {
+        let mut seller_amount = coin.amount;
+
+        if let Some(val) = &config.platform_fee {
+            for (i, fee_amount) in val.split(coin.amount).into_iter().enumerate() {
+                if !fee_amount.is_zero() {
+                    fees[i].push(Coin::new(fee_amount.u128(), coin.denom.clone()));
+                }
+                seller_amount -= fee_amount;
+            }
+        }
+
+        let royalty_amount = match &royalty {
+            Some(val) => coin.amount * val.share,
+            None => Uint128::zero(),
+        };
+        if !royalty_amount.is_zero() {
+            royalties.push(Coin::new(royalty_amount.u128(), coin.denom.clone()));
+        }
+
+        seller_amount -= royalty_amount;
+        if !seller_amount.is_zero() {
+            proceeds.push(Coin::new(seller_amount.u128(), coin.denom.clone()));
+        }
+    }
+
+    let mut msgs = vec![];
+    for (recipient, amount) in fee_recipients.into_iter().zip(fees) {
+        if !amount.is_empty() {
+            msgs.push(BankMsg::Send {
+                to_address: recipient.address,
+                amount,
+            });
+        }
+    }
+    if let Some(val) = royalty {
+        if !royalties.is_empty() {
+            msgs.push(BankMsg::Send {
+                to_address: val.payment_address,
+                amount: royalties,
+            });
+        }
+    }
+    if !proceeds.is_empty() {
+        msgs.push(BankMsg::Send {
+            to_address: seller.to_string(),
+            amount: proceeds,
+        });
+    }
+    Ok(msgs)
+}
+
+/// Same as `sale_payouts`, for a sale paid in a CW20 token.
+fn cw20_sale_payouts(
+    deps: Deps,
+    token: &TokenInfo,
+    seller: &Addr,
+    price: &Cw20CoinVerified,
+) -> StdResult<Vec<CosmosMsg>> {
+    let config = query_config(deps)?;
+    let royalty = token_royalty(deps, token)?;
+    let cw20 = Cw20Contract(price.address.clone());
+
+    let mut msgs = vec![];
+    let mut seller_amount = price.amount;
+    if let Some(val) = config.platform_fee {
+        let shares = val.split(price.amount);
+        for (recipient, fee_amount) in val.recipients.into_iter().zip(shares) {
+            if !fee_amount.is_zero() {
+                msgs.push(cw20.call(Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.address,
+                    amount: fee_amount,
+                })?);
+            }
+            seller_amount -= fee_amount;
+        }
+    }
+
+    if let Some(val) = royalty {
+        let royalty_amount = price.amount * val.share;
+        if !royalty_amount.is_zero() {
+            msgs.push(cw20.call(Cw20ExecuteMsg::Transfer {
+                recipient: val.payment_address,
+                amount: royalty_amount,
+            })?);
+        }
+        seller_amount -= royalty_amount;
+    }
+
+    if !seller_amount.is_zero() {
+        msgs.push(cw20.call(Cw20ExecuteMsg::Transfer {
+            recipient: seller.to_string(),
+            amount: seller_amount,
+        })?);
+    }
+    Ok(msgs)
+}
+
+pub fn handle_set_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    price: Vec<Coin>,
+    price_mode: Option<PriceMode>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    validate_price(&price)?;
+
+    token.base_price = price;
+    if let Some(val) = price_mode {
+        token.price_mode = val;
+    }
+    token.cw20_price = None;
+    token.listed = true;
+    save_token(deps.storage, &env.block, &token)?;
+    DUTCH_AUCTIONS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "set_price")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_set_cw20_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    price: Cw20Coin,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    token.cw20_price = Some(validate_cw20_price(deps.as_ref(), price)?);
+    token.listed = true;
+    save_token(deps.storage, &env.block, &token)?;
+    DUTCH_AUCTIONS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "set_cw20_price")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_delist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+
+    token.listed = false;
+    save_token(deps.storage, &env.block, &token)?;
+    DUTCH_AUCTIONS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "delist")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_start_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    reserve_price: Coin,
+    min_increment: Uint128,
+    ends_at: Expiration,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    if reserve_price.amount.is_zero() {
+        return Err(ContractError::CustomError {
+            val: String::from("Reserve price cannot be zero"),
+        });
+    }
+
+    // auction has to end at some point to be settled
+    if let Expiration::Never {} = ends_at {
+        return Err(ContractError::CustomError {
+            val: String::from("Auction must have an end"),
+        });
+    }
+    if ends_at.is_expired(&env.block) {
+        return Err(ContractError::Expired);
+    }
+
+    let auction = Auction {
+        token_id: token.external_id(),
+        seller: token.owner,
+        reserve_price,
+        min_increment,
+        ends_at,
+        highest_bid: None,
+        bid_count: 0,
+    };
+    AUCTIONS.save(deps.storage, token_id, &auction)?;
+    // A running Dutch auction is replaced by the English one
+    DUTCH_AUCTIONS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "start_auction")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", auction.token_id))
+}
+
+pub fn handle_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut auction = AUCTIONS.load(deps.storage, token_id)?;
+
+    if auction.ends_at.is_expired(&env.block) {
+        return Err(ContractError::AuctionEnded { token_id });
+    }
+
+    if auction.seller == info.sender {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot bid on your own token"),
+        });
+    }
+
+    // the whole bid history is removed at settlement
+    if auction.bid_count >= MAX_BIDS_PER_AUCTION {
+        return Err(ContractError::TooManyBids {
+            max: MAX_BIDS_PER_AUCTION,
+        });
+    }
+
+    // First bid has to meet the reserve price, the following ones have to
+    // beat the highest bid by at least the minimum increment
+    let denom = auction.reserve_price.denom.clone();
+    let amount = must_pay(&info, &denom)?;
+    let min = match &auction.highest_bid {
+        Some(val) => val.amount.amount + auction.min_increment.max(Uint128::new(1)),
+        None => auction.reserve_price.amount,
+    };
+    if amount < min {
+        return Err(ContractError::BidTooLow {
+            min: Coin::new(min.u128(), denom),
+        });
+    }
+
+    let bid = Bid {
+        bidder: info.sender.clone(),
+        amount: Coin::new(amount.u128(), denom),
+        placed_at: env.block.time,
+    };
+    BIDS.save(deps.storage, (token_id, auction.bid_count), &bid)?;
+
+    let mut res = Response::new();
+    // Refund the bidder who has been outbid
+    if let Some(val) = auction.highest_bid.replace(bid) {
+        res = res.add_message(BankMsg::Send {
+            to_address: val.bidder.to_string(),
+            amount: vec![val.amount],
+        });
+    }
+    auction.bid_count += 1;
+    AUCTIONS.save(deps.storage, token_id, &auction)?;
+
+    Ok(res
+        .add_attribute("action", "place_bid")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", auction.token_id)
+        .add_attribute("amount", amount))
+}
+
+pub fn handle_cancel_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.load(deps.storage, token_id)?;
+    let token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+
+    if auction.highest_bid.is_some() {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot cancel an auction which has bids"),
+        });
+    }
+
+    AUCTIONS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_auction")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_settle_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let auction = AUCTIONS.load(deps.storage, token_id)?;
+
+    if !auction.ends_at.is_expired(&env.block) {
+        return Err(ContractError::AuctionNotEnded { token_id });
+    }
+
+    remove_auction(deps.storage, token_id)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "settle_auction")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", auction.token_id.clone());
+
+    // Without any bids the token simply stays with its owner
+    if let Some(bid) = auction.highest_bid {
+        let mut token = query_tokens(deps.as_ref(), token_id)?;
+        let seller = token.owner.clone();
+        transfer_token(deps.storage, &mut token, bid.bidder.clone())?;
+
+        let payments = sale_payouts(deps.as_ref(), &token, &seller, &[bid.amount])?;
+        res = res
+            .add_messages(payments)
+            .add_attribute("winner", bid.bidder);
+    }
+    Ok(res)
+}
+
+pub fn handle_start_dutch_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    mut auction: DutchAuction,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_approve(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    if auction.floor_price.is_zero() || auction.start_price.amount <= auction.floor_price {
+        return Err(ContractError::CustomError {
+            val: String::from("Start price has to be above a non-zero floor price"),
+        });
+    }
+
+    if auction.end_time <= auction.start_time || auction.end_time <= env.block.time {
+        return Err(ContractError::CustomError {
+            val: String::from("Dutch auction has to end after it starts and in the future"),
+        });
+    }
+
+    if let DecayCurve::Stepwise { interval: 0 } = auction.curve {
+        return Err(ContractError::CustomError {
+            val: String::from("Step interval cannot be zero"),
+        });
+    }
+
+    // The Dutch auction replaces the fixed price listing
+    token.listed = false;
+    save_token(deps.storage, &env.block, &token)?;
+
+    auction.token_id = token.external_id();
+    DUTCH_AUCTIONS.save(deps.storage, token_id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_dutch_auction")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", auction.token_id))
+}
+
+pub fn handle_make_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let token = query_tokens(deps.as_ref(), token_id)?;
+
+    ensure_transferable(&token)?;
+    if token.owner == info.sender {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot make an offer on your own token"),
+        });
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot make an offer that is already expired"),
+        });
+    }
+
+    let amount: Vec<Coin> = info
+        .funds
+        .iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .cloned()
+        .collect();
+    if amount.is_empty() {
+        return Err(PaymentError::NoFunds {}.into());
+    }
+
+    let offer = Offer {
+        token_id: token.external_id(),
+        bidder: info.sender.clone(),
+        amount,
+        expires,
+    };
+
+    let mut res = Response::new();
+    // Refund the offer being replaced
+    let key = (token_id, &info.sender);
+    if let Some(val) = offers().may_load(deps.storage, key)? {
+        res = res.add_message(BankMsg::Send {
+            to_address: val.bidder.to_string(),
+            amount: val.amount,
+        });
+    }
+    offers().save(deps.storage, key, &offer)?;
+
+    Ok(res
+        .add_attribute("action", "make_offer")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_cancel_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    let key = (token_id, &info.sender);
+    let offer =
+        offers()
+            .may_load(deps.storage, key)?
+            .ok_or_else(|| ContractError::OfferNotFound {
+                token_id,
+                bidder: info.sender.to_string(),
+            })?;
+    offers().remove(deps.storage, key)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: offer.bidder.to_string(),
+            amount: offer.amount,
+        })
+        .add_attribute("action", "cancel_offer")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", offer.token_id))
+}
+
+pub fn handle_accept_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    bidder: String,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+
+    authorized_to_send(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    let bidder_addr = deps.api.addr_validate(&bidder)?;
+    let key = (token_id, &bidder_addr);
+    let offer =
+        offers()
+            .may_load(deps.storage, key)?
+            .ok_or_else(|| ContractError::OfferNotFound {
+                token_id,
+                bidder: bidder.clone(),
+            })?;
+
+    if offer.expires.is_expired(&env.block) {
+        return Err(ContractError::OfferExpired { token_id, bidder });
+    }
+    // The bidder may have received the token in the meantime
+    if token.owner == bidder_addr {
+        return Err(ContractError::CustomError {
+            val: String::from("Bidder already owns the token"),
+        });
+    }
+    offers().remove(deps.storage, key)?;
+
+    let seller = token.owner.clone();
+    transfer_token(deps.storage, &mut token, bidder_addr.clone())?;
+
+    // Pay the royalty and the previous owner out of the escrowed funds
+    let payments = sale_payouts(deps.as_ref(), &token, &seller, &offer.amount)?;
+
+    Ok(Response::new()
+        .add_messages(payments)
+        .add_attribute("action", "accept_offer")
+        .add_attribute("from", seller)
+        .add_attribute("to", bidder_addr)
+        .add_attribute("token_id", token.external_id()))
+}
+
+pub fn handle_make_collection_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price: Coin,
+    quantity: u32,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    if price.amount.is_zero() || quantity == 0 {
+        return Err(ContractError::CustomError {
+            val: String::from("Price and quantity of an offer cannot be zero"),
+        });
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::CustomError {
+            val: String::from("Cannot make an offer that is already expired"),
+        });
+    }
+
+    // Funds for every token have to be sent upfront
+    let total = price
+        .amount
+        .checked_mul(quantity.into())
+        .map_err(StdError::from)?;
+    let total = Coin::new(total.u128(), price.denom.clone());
+    let paid = must_pay(&info, &price.denom)?;
+    if paid != total.amount {
+        return Err(ContractError::InvalidAmount {
+            val: total,
+            funds: Coin::new(paid.u128(), price.denom),
+        });
+    }
+
+    let mut config = query_config(deps.as_ref())?;
+    let offer_id = config.next_collection_offer_id;
+    config.next_collection_offer_id += 1;
+    CONFIG.save(deps.storage, &config)?;
+
+    let offer = CollectionOffer {
+        offer_id,
+        bidder: info.sender.clone(),
+        price,
+        quantity,
+        expires,
+    };
+    collection_offers().save(deps.storage, offer_id, &offer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "make_collection_offer")
+        .add_attribute("from", info.sender)
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+pub fn handle_cancel_collection_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let offer = collection_offers().load(deps.storage, offer_id)?;
+
+    if offer.bidder != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+    collection_offers().remove(deps.storage, offer_id)?;
+
+    // Refund the tokens which have not been bought
+    let refund = offer.price.amount * Uint128::from(offer.quantity);
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: offer.bidder.to_string(),
+            amount: vec![Coin::new(refund.u128(), offer.price.denom)],
+        })
+        .add_attribute("action", "cancel_collection_offer")
+        .add_attribute("from", info.sender)
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+pub fn handle_accept_collection_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref(), PauseTarget::Marketplace)?;
+
+    let mut token = query_tokens(deps.as_ref(), token_id)?;
+    let mut offer = collection_offers().load(deps.storage, offer_id)?;
+
+    authorized_to_send(deps.as_ref(), &env, &info, &token)?;
+    ensure_transferable(&token)?;
+    ensure_not_in_auction(deps.as_ref(), token_id)?;
+
+    if offer.expires.is_expired(&env.block) {
+        return Err(ContractError::CollectionOfferExpired { offer_id });
+    }
+    if token.owner == offer.bidder {
+        return Err(ContractError::CustomError {
+            val: String::from("Bidder already owns the token"),
+        });
+    }
+
+    // Keep the offer around until every token has been bought
+    offer.quantity -= 1;
+    if offer.quantity == 0 {
+        collection_offers().remove(deps.storage, offer_id)?;
+    } else {
+        collection_offers().save(deps.storage, offer_id, &offer)?;
+    }
+
+    let seller = token.owner.clone();
+    transfer_token(deps.storage, &mut token, offer.bidder.clone())?;
+
+    // Pay the royalty and the previous owner out of the escrowed funds
+    let payments = sale_payouts(deps.as_ref(), &token, &seller, &[offer.price])?;
+
+    Ok(Response::new()
+        .add_messages(payments)
+        .add_attribute("action", "accept_collection_offer")
+        .add_attribute("from", seller)
+        .add_attribute("to", offer.bidder)
+        .add_attribute("token_id", token.external_id())
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+/// Remove the auction of the token along with its bid history.
+fn remove_auction(storage: &mut dyn Storage, token_id: u64) -> StdResult<()> {
+    let bids: StdResult<Vec<_>> = BIDS
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect();
+    for val in bids? {
+        BIDS.remove(storage, (token_id, val));
+    }
+
+    AUCTIONS.remove(storage, token_id);
+    Ok(())
+}
+
+/// Soulbound tokens can only be burned.
+fn ensure_transferable(token: &TokenInfo) -> Result<(), ContractError> {
+    if !token.transferable {
+        return Err(ContractError::NonTransferable {
+            token_id: token.token_id,
+        });
+    }
+    Ok(())
+}
+
+/// Tokens cannot be moved, approved or sold while they are being auctioned.
+fn ensure_not_in_auction(deps: Deps, token_id: u64) -> Result<(), ContractError> {
+    if AUCTIONS.has(deps.storage, token_id) {
+        return Err(ContractError::InAuction { token_id });
+    }
+    Ok(())
+}
+
+pub fn handle_set_royalty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: Option<u64>,
+    royalty: Option<Royalty>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the minter can change royalties
+    if config.minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if let Some(val) = &royalty {
+        validate_royalty(deps.as_ref(), val)?;
+    }
+
+    let target = match token_id {
+        Some(val) => {
+            let mut token = query_tokens(deps.as_ref(), val)?;
+            token.royalty = royalty;
+            save_token(deps.storage, &env.block, &token)?;
+            token.external_id()
+        }
+        None => {
+            config.default_royalty = royalty;
+            CONFIG.save(deps.storage, &config)?;
+            String::from("default")
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "set_royalty")
+        .add_attribute("from", info.sender)
+        .add_attribute("token_id", target))
+}
+
+pub fn handle_pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    targets: Option<Vec<PauseTarget>>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can pause the contract
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let targets = targets.unwrap_or_else(|| PauseTarget::ALL.to_vec());
+    config.paused.extend(&targets);
+    config.paused.sort_unstable();
+    config.paused.dedup();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("from", info.sender)
+        .add_event(Event::new("pause").add_attribute("targets", join_targets(&targets))))
+}
+
+pub fn handle_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    targets: Option<Vec<PauseTarget>>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can unpause the contract
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let targets = targets.unwrap_or_else(|| PauseTarget::ALL.to_vec());
+    config.paused.retain(|val| !targets.contains(val));
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("from", info.sender)
+        .add_event(Event::new("unpause").add_attribute("targets", join_targets(&targets))))
+}
+
+fn join_targets(targets: &[PauseTarget]) -> String {
+    targets
+        .iter()
+        .map(PauseTarget::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn ensure_not_paused(deps: Deps, target: PauseTarget) -> Result<(), ContractError> {
+    if query_config(deps)?.paused.contains(&target) {
+        return Err(ContractError::Paused { target });
+    }
+    Ok(())
+}
+
+pub fn handle_update_platform_fee(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    platform_fee: Option<PlatformFee>,
+) -> Result<Response, ContractError> {
+    let mut config = query_config(deps.as_ref())?;
+
+    // only the admin can change the fee
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if let Some(val) = &platform_fee {
+        validate_platform_fee(deps.as_ref(), val)?;
+    }
+    config.platform_fee = platform_fee;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_platform_fee")
+        .add_attribute("from", info.sender))
+}
+
+/// Platform fee cannot exceed the maximum and has to be split between
+/// valid addresses with non-zero weights.
+fn validate_platform_fee(deps: Deps, fee: &PlatformFee) -> Result<(), ContractError> {
+    if fee.bps > MAX_PLATFORM_FEE_BPS {
+        return Err(ContractError::FeeTooHigh {
+            max_bps: MAX_PLATFORM_FEE_BPS,
+        });
+    }
+
+    if fee.recipients.is_empty() {
+        return Err(ContractError::CustomError {
+            val: String::from("Platform fee needs at least one recipient"),
+        });
+    }
+
+    for recipient in fee.recipients.iter() {
+        deps.api.addr_validate(&recipient.address)?;
+        if recipient.weight == 0 {
+            return Err(ContractError::CustomError {
+                val: String::from("Weight of a fee recipient cannot be zero"),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Royalty has to be paid to a valid address and cannot exceed the
+/// maximum share.
+fn validate_royalty(deps: Deps, royalty: &Royalty) -> Result<(), ContractError> {
+    deps.api.addr_validate(&royalty.payment_address)?;
+
+    let max = Decimal::percent(MAX_ROYALTY_PERCENT);
+    if royalty.share > max {
+        return Err(ContractError::RoyaltyTooHigh { max });
+    }
+    Ok(())
+}
+
+/// Price of a token cannot be empty and none of its coins can be zero.
+fn validate_price(price: &[Coin]) -> Result<(), ContractError> {
+    if price.is_empty() {
+        return Err(ContractError::CustomError {
+            val: String::from("Token price cannot be empty"),
+        });
+    }
+
+    for (i, val) in price.iter().enumerate() {
+        if val.amount.is_zero() {
+            return Err(ContractError::CustomError {
+                val: String::from("Token price cannot be zero"),
+            });
+        }
+
+        // A denom can only appear once, otherwise the amount to pay is ambiguous
+        if price[..i].iter().any(|coin| coin.denom == val.denom) {
+            return Err(ContractError::CustomError {
+                val: format!("Token price contains denom {} more than once", val.denom),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// CW20 prices have to be non-zero and point to a valid address.
+/// Mint phases are priced either in native coins or, when `cw20_price` is
+/// set, in a CW20 token whose address is normalized on the way.
+fn validate_mint_price(
+    deps: Deps,
+    price: &[Coin],
+    cw20_price: &mut Option<Cw20Coin>,
+) -> Result<(), ContractError> {
+    match cw20_price.take() {
+        Some(val) => {
+            let val = validate_cw20_price(deps, val)?;
+            *cw20_price = Some(Cw20Coin {
+                address: val.address.into_string(),
+                amount: val.amount,
+            });
+            Ok(())
+        }
+        None => validate_price(price),
+    }
+}
+
+fn validate_cw20_price(deps: Deps, price: Cw20Coin) -> Result<Cw20CoinVerified, ContractError> {
+    if price.amount.is_zero() {
+        return Err(ContractError::CustomError {
+            val: String::from("Token price cannot be zero"),
+        });
+    }
+
+    Ok(Cw20CoinVerified {
+        address: deps.api.addr_validate(&price.address)?,
+        amount: price.amount,
+    })
+}
+
+/// Check that `funds` pays `price` according to `mode`, and return the
+/// coins which have been paid.
+///
+/// With `PriceMode::AllOf` every coin has to be paid exactly. With
+/// `PriceMode::AnyOf` exactly one of the coins has to be paid. Denoms
+/// which are not part of the price are rejected in both cases, as they
+/// would be stuck in the contract.
+fn check_funds(
+    price: &[Coin],
+    mode: PriceMode,
+    funds: &[Coin],
+) -> Result<Vec<Coin>, ContractError> {
+    let funds: Vec<&Coin> = funds.iter().filter(|coin| !coin.amount.is_zero()).collect();
+
+    for coin in funds.iter() {
+        if !price.iter().any(|val| val.denom == coin.denom) {
+            return Err(ContractError::UnexpectedDenom {
+                denom: coin.denom.clone(),
+            });
+        }
+    }
+
+    let check_amount = |val: &Coin, sent: &Coin| {
+        if sent.amount != val.amount {
+            return Err(ContractError::InvalidAmount {
+                val: val.clone(),
+                funds: sent.clone(),
+            });
+        }
+        Ok(())
+    };
+
+    match mode {
+        PriceMode::AllOf => {
+            for val in price.iter() {
+                let sent = funds
+                    .iter()
+                    .find(|coin| coin.denom == val.denom)
+                    .ok_or_else(|| ContractError::MissingDenom {
+                        denom: val.denom.clone(),
+                    })?;
+                check_amount(val, sent)?;
+            }
+            Ok(price.to_vec())
+        }
+        PriceMode::AnyOf => {
+            let sent = match funds.as_slice() {
+                [] => return Err(PaymentError::NoFunds {}.into()),
+                [coin] => *coin,
+                _ => return Err(PaymentError::MultipleDenoms {}.into()),
+            };
+            let val = price
+                .iter()
+                .find(|val| val.denom == sent.denom)
+                .ok_or_else(|| ContractError::UnexpectedDenom {
+                    denom: sent.denom.clone(),
+                })?;
+            check_amount(val, sent)?;
+            Ok(vec![val.clone()])
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::needless_borrows_for_generic_args,
+    clippy::unneeded_struct_pattern
+)]
+mod tests {
+    use super::*;
+    use crate::msg::FeeRecipient;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{attr, coins, to_binary, Addr, CosmosMsg, StdError, WasmMsg};
+    use cw721::Expiration;
+
+    const DENOM: &str = "ubit";
+
+    fn init_msg(name: String, symbol: String) -> InstantiateMsg {
+        InstantiateMsg {
+            name,
+            symbol,
+            minter: None,
+            admin: None,
+            max_supply: None,
+        }
+    }
+
+    fn approvals_of(deps: Deps, token_id: u64) -> Vec<Approval> {
+        token_approvals()
+            .prefix(token_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| res.unwrap().1)
+            .collect()
+    }
+
+    // Config as laid out before v0.4.0
+    fn old_config(token_count: u64) -> crate::migrations::v0_3::State {
+        crate::migrations::v0_3::State {
+            name: String::from("TestNFT"),
+            symbol: String::from("NFT"),
+            minter: Some(Addr::unchecked("minter")),
+            pending_minter: None,
+            admin: Addr::unchecked("minter"),
+            next_token_id: token_count + 1,
+            token_count,
+            max_supply: None,
+            public_mint: None,
+            allowlist: None,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            default_royalty: None,
+            next_collection_offer_id: 1,
+            platform_fee: None,
+        }
+    }
+
+    fn mint_msg(owner: String) -> MintMsg {
+        MintMsg {
+            token_id: None,
+            owner,
+            token_uri: None,
+            price: coins(1000, &DENOM.to_string()),
+            extension: None,
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        }
+    }
+
+    #[test]
+    fn proper_initialization() {
+        // Create mock dependencies and environment
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+
+        // Successful instantiation
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let stored_state = query_config(deps.as_ref()).unwrap();
+        assert_eq!(stored_state.name, "TestNFT");
+        assert_eq!(stored_state.symbol, "NFT");
+        assert_eq!(stored_state.next_token_id, 1u64);
+        assert_eq!(stored_state.token_count, 0u64);
+        assert_eq!(stored_state.minter, Some(Addr::unchecked("creator")));
+        assert_eq!(stored_state.pending_minter, None);
+
+        // Following tests are to check correct error when no value is given
+        // to either of the fields in InstantiateMsg.
+        // * Missing name
+        let msg = init_msg(String::new(), "NFT".to_string());
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        }
+
+        // * Missing symbol
+        let msg = init_msg(String::from("TestNFT"), String::new());
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        }
+
+        // * Missing name and symbol
+        let msg = init_msg(String::from(""), String::from(""));
+        let res = instantiate(deps.as_mut(), env, info, msg).unwrap_err();
+
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn initialization_with_minter() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &coins(0, DENOM.to_string()));
+
+        let msg = InstantiateMsg {
+            name: String::from("TestNFT"),
+            symbol: String::from("NFT"),
+            minter: Some(String::from("minter")),
+            admin: None,
+            max_supply: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let stored_state = query_config(deps.as_ref()).unwrap();
+        assert_eq!(stored_state.minter, Some(Addr::unchecked("minter")));
+
+        // creator is not allowed to mint
+        let res = handle_mint(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            mint_msg("owner".to_string()),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn mint() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Successful token minting
+        let mint_msg = mint_msg("creator".to_string());
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), mint_msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+
+        let stored_token = query_tokens(deps.as_ref(), 1).unwrap();
+        assert_eq!(stored_token.owner, "creator");
+        assert_eq!(stored_token.token_id, 1);
+        assert_eq!(stored_token.base_price, coins(1000, DENOM.to_string()));
+        assert_eq!(approvals_of(deps.as_ref(), 1), vec![]);
+        assert_eq!(stored_token.token_uri, None);
+        assert_eq!(stored_token.extension, None);
+
+        // Unsuccessful token minting
+        // * owner name is empty
+        let msg = MintMsg {
+            token_id: None,
+            owner: String::new(),
+            token_uri: None,
+            price: coins(1000, DENOM.to_string()),
+            extension: None,
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        };
+
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::GenericErr { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * amount is empty i.e., price has been provided as 0Denom
+        let msg = MintMsg {
+            token_id: None,
+            owner: String::from("owner"),
+            token_uri: None,
+            price: coins(0, DENOM.to_string()),
+            extension: None,
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        };
+
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic! {"{:?}", e},
+        };
+
+        // * price is empty
+        let msg = MintMsg {
+            token_id: None,
+            owner: String::from("owner"),
+            token_uri: None,
+            price: vec![],
+            extension: None,
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        };
+
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic! {"{:?}", e},
+        };
+    }
+
+    #[test]
+    fn mint_with_custom_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Successful token minting
+        let mut msg = mint_msg("creator".to_string());
+        msg.token_id = Some(String::from("sword"));
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(res.attributes[3].value, "sword");
+
+        let token_id = resolve_token_id(deps.as_ref(), "sword").unwrap();
+        assert_eq!(token_id, 1);
+        let token = query_tokens(deps.as_ref(), token_id).unwrap();
+        assert_eq!(token.custom_id, Some(String::from("sword")));
+        assert_eq!(token.external_id(), "sword");
+
+        // Unsuccessful token minting
+        // * custom id already claimed
+        let mut msg = mint_msg("creator".to_string());
+        msg.token_id = Some(String::from("sword"));
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Claimed { token_id } => assert_eq!(token_id, "sword"),
+            e => panic!("{:?}", e),
+        };
+
+        // * numeric custom id
+        let mut msg = mint_msg("creator".to_string());
+        msg.token_id = Some(String::from("42"));
+        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * empty custom id
+        let mut msg = mint_msg("creator".to_string());
+        msg.token_id = Some(String::new());
+        let res = handle_mint(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Custom id can be used in messages
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::SendNft {
+            contract: String::from("contract"),
+            token_id: String::from("sword"),
+            msg: Binary::from(b"hello".to_vec()),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let expected = Cw721ReceiveMsg {
+            sender: String::from("creator"),
+            token_id: String::from("sword"),
+            msg: Binary::from(b"hello".to_vec()),
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            expected.into_cosmos_msg("contract").unwrap()
+        );
+        assert_eq!(res.attributes[3], attr("token_id", "sword"));
+
+        let token = query_tokens(deps.as_ref(), 1).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("contract"));
+
+        // * unknown custom id
+        let info = mock_info("contract", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("shield"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * numeric id of a token minted with a custom id
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("sword"),
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.attributes[3], attr("token_id", "sword"));
+    }
+
+    #[test]
+    fn approve() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        env.block.height = 50u64;
+        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let mint_msg = mint_msg("owner1".to_string());
+        handle_mint(deps.as_mut(), env.clone(), info, mint_msg).unwrap();
+
+        // Successful approval request
+        // * by owner
+        let approve_msg = ExecuteMsg::Approve {
+            operator: "operator".to_string(),
+            token_id: String::from("1"),
+            expires: None,
+        };
+
+        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 4);
+
+        let approvals = approvals_of(deps.as_ref(), 1);
+        assert_eq!(approvals[0].operator, Addr::unchecked("operator"));
+        assert_eq!(approvals[0].expires, Expiration::Never {});
+
+        // * by operator
+
+        // Approve all for operator1
+        let res = handle_approve_all(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator1"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 0);
+
+        // operator1 approves user1
+        let info = mock_info("operator1", &coins(0, &DENOM.to_string()));
+        let res = handle_approve(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            "user1",
+            1u64,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 4);
+        assert_eq!(res.attributes[1].value, String::from("operator1"));
+
+        let approvals = approvals_of(deps.as_ref(), 1);
+        assert_eq!(approvals.len(), 2);
+        assert_eq!(approvals[1].operator, Addr::unchecked("user1"));
+
+        // Unsuccessful approval request
+        // * empty operator field
+        let approve_msg = ExecuteMsg::Approve {
+            operator: String::new(),
+            token_id: String::from("1"),
+            expires: None,
+        };
+        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::GenericErr { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * Invalid token
+        let approve_msg = ExecuteMsg::Approve {
+            operator: "operator".to_string(),
+            token_id: String::from("2"),
+            expires: None,
+        };
+        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
+        execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+
+        // * expired approval
+        let approve_msg = ExecuteMsg::Approve {
+            operator: "operator".to_string(),
+            token_id: String::from("1"),
+            expires: Some(Expiration::AtHeight(45u64)),
+        };
+        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+        match res {
+            ContractError::Expired {} => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn approve_all() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        env.block.height = 25u64;
+        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Successful approval for operator
+        // * new operator
+        let operator = String::from("operator");
+        let expires = None;
+
+        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
+            .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 3);
+
+        let res = OPERATORS
+            .load(&deps.storage, (&info.sender, &Addr::unchecked("operator")))
+            .unwrap();
+        assert_eq!(res, Expiration::Never {});
+
+        // * update expiration
+        let operator = String::from("operator");
+        let expires = Some(Expiration::AtHeight(130_000));
+
+        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
+            .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 3);
+
+        // Unsuccessful approval for operator
+        // * Operator exists
+        let operator = String::from("operator");
+        let expires = Some(Expiration::AtHeight(130_000));
+        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
+            .unwrap_err();
+
+        match res {
+            ContractError::OperatorApproved { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * Expired height
+        let operator = String::from("operator");
+        let expires = Some(Expiration::AtHeight(24u64));
+
+        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
+            .unwrap_err();
+        match res {
+            ContractError::Expired => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * Expired time
+        let operator = String::from("operator");
+        let expires = Some(Expiration::AtTime(env.block.time.minus_seconds(64u64)));
+
+        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
+            .unwrap_err();
+        match res {
+            ContractError::Expired => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn revoke() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Mint a new token
+        let mint_msg = mint_msg("owner".to_string());
+        let _res = handle_mint(deps.as_mut(), env.clone(), info.clone(), mint_msg).unwrap();
+
+        // Approve operator1
+        let approve_msg = ExecuteMsg::Approve {
+            operator: "operator1".to_string(),
+            token_id: String::from("1"),
+            expires: None,
+        };
+        let info = mock_info("owner", &coins(0, &DENOM.to_string()));
+        execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap();
+
+        // Approve operator2
+        handle_approve(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            "operator2",
+            1u64,
+            None,
+        )
+        .unwrap();
+
+        let approvals = approvals_of(deps.as_ref(), 1);
+        assert_eq!(approvals[0].operator, Addr::unchecked("operator1"));
+        assert_eq!(approvals[0].expires, Expiration::Never {});
+
+        // Successful approval revoke
+        // * by owner
+        let revoke_msg = ExecuteMsg::Revoke {
+            operator: "operator1".to_string(),
+            token_id: String::from("1"),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 4);
+
+        assert_eq!(approvals_of(deps.as_ref(), 1).len(), 1);
+
+        // * by operator
+        handle_approve_all(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator"),
+            None,
+        )
+        .unwrap();
+
+        let info = mock_info("operator", &coins(0, &DENOM.to_string()));
+        handle_revoke(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator2"),
+            1u64,
+        )
+        .unwrap();
+
+        assert_eq!(approvals_of(deps.as_ref(), 1).len(), 0);
+
+        // Unsuccessful approval revoke
+        // * Invalid token id
+        let revoke_msg = ExecuteMsg::Revoke {
+            operator: "operator".to_string(),
+            token_id: String::from("2"),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * Approval being revoked for an address that isn't approved.
+        let revoke_msg = ExecuteMsg::Revoke {
+            operator: "unknown".to_string(),
+            token_id: String::from("1"),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 4);
+
+        // * Unauthorised sender
+        let info = mock_info("owner2", &coins(0, &DENOM.to_string()));
+        let revoke_msg = ExecuteMsg::Revoke {
+            operator: "operator".to_string(),
+            token_id: String::from("1"),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn revoke_all() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("owner", &coins(0u128, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), mock_info("minter", &[]), msg).unwrap();
+
+        // Approve an address for all tokens
+        let res = handle_approve_all(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 3);
+
+        // Check that the new approval exists in correct format
+        let key = (&Addr::unchecked("owner"), &Addr::unchecked("operator"));
+        assert!(OPERATORS.has(&deps.storage, key));
+        assert_eq!(
+            OPERATORS.load(&deps.storage, key).unwrap(),
+            Expiration::Never {}
+        );
+
+        // Revoke previously provided approval
+        let res = handle_revoke_all(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator"),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes.len(), 3);
+        assert!(!OPERATORS.has(&deps.storage, key));
+
+        // Unsuccessful revoke
+        let res = handle_revoke_all(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            String::from("operator"),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::ApprovalNotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn transfer_nft_by_owner() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let mint_msg = mint_msg("creator".to_string());
+
+        let msg = ExecuteMsg::Mint(mint_msg);
+        let res: Response = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+
+        let info = mock_info("creator", &coins(0u128, &DENOM.to_string()));
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        let res: Response = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+    }
+
+    #[test]
+    fn transfer_nft_by_operator() {
+        // Setup the necessary environment
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let mint_msg = mint_msg("creator".to_string());
+
+        let msg = ExecuteMsg::Mint(mint_msg);
+        let res: Response = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+
+        // Add approval to the token
+        let operator = Addr::unchecked("operator");
+        let appr = Approval {
+            operator: operator.clone(),
+            expires: Expiration::Never {},
+        };
+        token_approvals()
+            .save(&mut deps.storage, (1u64, &operator), &appr)
+            .unwrap();
+
+        // *operator* should now be capable of transferring the token
+        let info = mock_info("operator", &coins(0u128, &DENOM.to_string()));
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        let res: Response = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+    }
+
+    #[test]
+    fn buy() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Unsuccessful purchase
+        // * owner buying their own token
+        let info = mock_info("creator", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * insufficient amount
+        let info = mock_info("buyer", &coins(999u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::InvalidAmount { val, funds } => {
+                assert_eq!(val, Coin::new(1000, DENOM));
+                assert_eq!(funds, Coin::new(999, DENOM));
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // * wrong denom
+        let info = mock_info("buyer", &coins(1000u128, "uatom"));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::UnexpectedDenom { denom } => assert_eq!(denom, "uatom"),
+            e => panic!("{:?}", e),
+        };
+
+        // * no funds
+        let info = mock_info("buyer", &[]);
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::MissingDenom { denom } => assert_eq!(denom, DENOM),
+            e => panic!("{:?}", e),
+        };
+
+        // * invalid token
+        let info = mock_info("buyer", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("2"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Approve an operator, which should be cleared on purchase
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        handle_approve(deps.as_mut(), env.clone(), info, "operator", 1u64, None).unwrap();
+
+        // Successful purchase
+        let info = mock_info("buyer", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(1000, DENOM.to_string()),
+            })
+        );
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("buyer"));
+        assert_eq!(approvals_of(deps.as_ref(), 1), vec![]);
+        assert!(!token.listed);
+
+        // * token is no longer listed after the purchase
+        let info = mock_info("buyer2", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        match res {
+            ContractError::NotListed { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn royalties() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let minter_info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap();
+
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+            execute(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap();
+        }
+
+        // Unsuccessful royalty update
+        // * sender is not the minter
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: None,
+            royalty: Some(Royalty {
+                payment_address: String::from("creator"),
+                share: Decimal::percent(10),
+            }),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * share above the maximum
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: None,
+            royalty: Some(Royalty {
+                payment_address: String::from("artist"),
+                share: Decimal::percent(MAX_ROYALTY_PERCENT + 1),
+            }),
+        };
+        let res = execute(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::RoyaltyTooHigh { max } => {
+                assert_eq!(max, Decimal::percent(MAX_ROYALTY_PERCENT))
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // Successful royalty update
+        // * collection default
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: None,
+            royalty: Some(Royalty {
+                payment_address: String::from("artist"),
+                share: Decimal::percent(10),
+            }),
+        };
+        let res = execute(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap();
+        assert_eq!(res.attributes[2].value, "default");
+
+        // * token override
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: Some(String::from("2")),
+            royalty: Some(Royalty {
+                payment_address: String::from("collab"),
+                share: Decimal::percent(5),
+            }),
+        };
+        execute(deps.as_mut(), env.clone(), minter_info, msg).unwrap();
+
+        // Sale of token 1 pays the collection default
+        let info = mock_info("buyer", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(2, res.messages.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("artist"),
+                amount: coins(100, DENOM.to_string()),
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(900, DENOM.to_string()),
+            })
+        );
+
+        // Sale of token 2 pays its own royalty
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("2"),
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(2, res.messages.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("collab"),
+                amount: coins(50, DENOM.to_string()),
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(950, DENOM.to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn set_price_and_delist() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert!(token.listed);
+
+        // Successful delist
+        // * by owner
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Delist {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, res.attributes.len());
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert!(!token.listed);
+
+        // Successful price update
+        // * by operator
+        handle_approve_all(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            String::from("operator"),
+            None,
+        )
+        .unwrap();
+
+        let info = mock_info("operator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("1"),
+            price: coins(2000, DENOM.to_string()),
+            price_mode: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, res.attributes.len());
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert!(token.listed);
+        assert_eq!(token.base_price, coins(2000, DENOM.to_string()));
+
+        // Unsuccessful price update
+        // * zero price
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("1"),
+            price: coins(0, DENOM.to_string()),
+            price_mode: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * unauthorized sender, even with a token approval
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        handle_approve(deps.as_mut(), env.clone(), info, "spender", 1u64, None).unwrap();
+
+        let info = mock_info("spender", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("1"),
+            price: coins(1, DENOM.to_string()),
+            price_mode: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Unsuccessful delist
+        // * unauthorized sender
+        let msg = ExecuteMsg::Delist {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Transfer takes the token off sale
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert!(!token.listed);
+    }
+
+    #[test]
+    fn minter_rotation() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let minter_info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap();
+
+        // Unsuccessful proposal
+        // * sender is not the minter
+        let info = mock_info("other", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::ProposeMinter {
+            new_minter: String::from("other"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful proposal
+        let msg = ExecuteMsg::ProposeMinter {
+            new_minter: String::from("new_minter"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), minter_info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, res.attributes.len());
+
+        // Proposal alone does not change the minter
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.minter, Some(Addr::unchecked("minter")));
+        assert_eq!(config.pending_minter, Some(Addr::unchecked("new_minter")));
+
+        // Unsuccessful acceptance
+        // * sender is not the proposed minter
+        let info = mock_info("other", &coins(0u128, DENOM.to_string()));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::AcceptMinter {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful acceptance
+        let new_minter_info = mock_info("new_minter", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::AcceptMinter {};
+        execute(deps.as_mut(), env.clone(), new_minter_info.clone(), msg).unwrap();
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.minter, Some(Addr::unchecked("new_minter")));
+        assert_eq!(config.pending_minter, None);
+
+        // Old minter can no longer mint, new one can
+        let res = handle_mint(
+            deps.as_mut(),
+            env.clone(),
+            minter_info,
+            mint_msg("owner".to_string()),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+        handle_mint(
+            deps.as_mut(),
+            env.clone(),
+            new_minter_info.clone(),
+            mint_msg("owner".to_string()),
+        )
+        .unwrap();
+
+        // Renounce closes minting for good
+        let msg = ExecuteMsg::RenounceMinter {};
+        execute(deps.as_mut(), env.clone(), new_minter_info.clone(), msg).unwrap();
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.minter, None);
+
+        let res = handle_mint(
+            deps.as_mut(),
+            env.clone(),
+            new_minter_info.clone(),
+            mint_msg("owner".to_string()),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        let msg = ExecuteMsg::ProposeMinter {
+            new_minter: String::from("new_minter"),
+        };
+        let res = execute(deps.as_mut(), env, new_minter_info, msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn burn() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        // Unsuccessful burn
+        // * unauthorized sender
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful burn
+        // * by operator with approval on the token
+        let owner_info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        handle_approve(
+            deps.as_mut(),
+            env.clone(),
+            owner_info.clone(),
+            "operator",
+            1u64,
+            None,
+        )
+        .unwrap();
+
+        let operator_info = mock_info("operator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), operator_info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+        assert_eq!(res.attributes[0].value, "burn");
+
+        let res = query_tokens(deps.as_ref(), 1u64).unwrap_err();
+        match res {
+            StdError::NotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.token_count, 1);
+        assert_eq!(config.next_token_id, 3);
+
+        // * by owner
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("2"),
+        };
+        execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap();
+
+        // * burned token cannot be burned again
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("2"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info, msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // New mints never reuse a burned id
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.attributes[3].value, "3");
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.token_count, 1);
+        assert_eq!(config.next_token_id, 4);
+    }
+
+    #[test]
+    fn migrate_from_v0_1() {
+        use crate::migrations::v0_1;
+        use crate::msg::{QueryMsg, TokensResponse};
+        use crate::query::query;
+        use cosmwasm_std::from_binary;
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // Store the state as it was laid out by v0.1.0
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+        let old_config = v0_1::State {
+            name: String::from("TestNFT"),
+            symbol: String::from("NFT"),
+            minter: Addr::unchecked("minter"),
+            num_tokens: 2,
+        };
+        v0_1::CONFIG_V0_1
+            .save(&mut deps.storage, &old_config)
+            .unwrap();
+
+        for token_id in 1..=2u64 {
+            let old_token = v0_1::TokenInfo {
+                owner: Addr::unchecked("owner"),
+                approvals: vec![Approval {
+                    operator: Addr::unchecked("operator"),
+                    expires: Expiration::Never {},
+                }],
+                base_price: coins(1000, DENOM.to_string()),
+                token_uri: None,
+                token_id,
+            };
+            v0_1::TOKENS_V0_1
+                .save(&mut deps.storage, token_id, &old_token)
+                .unwrap();
+        }
+
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, res.attributes.len());
+
+        // State has been upgraded in place
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.name, "TestNFT");
+        assert_eq!(config.minter, Some(Addr::unchecked("minter")));
+        assert_eq!(config.next_token_id, 3);
+        assert_eq!(config.token_count, 2);
+
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("owner"));
+        assert_eq!(approvals_of(deps.as_ref(), 2).len(), 1);
+        assert_eq!(token.base_price, coins(1000, DENOM.to_string()));
+        assert!(token.listed);
+
+        // Owner index has been built for the existing tokens
+        let msg = QueryMsg::Tokens {
+            owner: String::from("owner"),
+            start_after: None,
+            limit: None,
+        };
+        let res: TokensResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.tokens, vec!["1", "2"]);
+
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        // Migrating again to the same version is a no-op
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.next_token_id, 3);
+        assert_eq!(config.token_count, 2);
+
+        // New mints continue after the migrated ids
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Mint(mint_msg("owner".to_string()));
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.attributes[3].value, "3");
+    }
+
+    #[test]
+    fn migrate_from_v0_2() {
+        use crate::migrations::{v0_2, v0_3};
+        use crate::msg::{ApprovalResponse, QueryMsg, TokensResponse};
+        use crate::query::query;
+        use cosmwasm_std::from_binary;
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // Store the state and a token as they were laid out by v0.2.0,
+        // with a duplicate approval
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.2.0").unwrap();
+        v0_3::CONFIG_V0_3
+            .save(&mut deps.storage, &old_config(1))
+            .unwrap();
+        let old_token = v0_2::TokenInfo {
+            owner: Addr::unchecked("owner"),
+            approvals: vec![
+                Approval {
+                    operator: Addr::unchecked("operator"),
+                    expires: Expiration::AtHeight(1),
+                },
+                Approval {
+                    operator: Addr::unchecked("operator"),
+                    expires: Expiration::Never {},
+                },
+                Approval {
+                    operator: Addr::unchecked("other"),
+                    expires: Expiration::Never {},
+                },
+            ],
+            base_price: coins(1000, DENOM),
+            price_mode: PriceMode::AllOf,
+            cw20_price: None,
+            listed: true,
+            transferable: true,
+            token_uri: None,
+            extension: None,
+            royalty: None,
+            token_id: 1,
+            custom_id: None,
+        };
+        v0_2::TOKENS_V0_2
+            .save(&mut deps.storage, 1u64, &old_token)
+            .unwrap();
+        let (owner, operator) = (Addr::unchecked("owner"), Addr::unchecked("market"));
+        OPERATORS
+            .save(
+                &mut deps.storage,
+                (&owner, &operator),
+                &Expiration::Never {},
+            )
+            .unwrap();
+
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert!(config.paused.is_empty());
+
+        // Approvals have moved out of the token, the last duplicate wins
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("owner"));
+        assert!(token.listed);
+        assert_eq!(
+            approvals_of(deps.as_ref(), 1),
+            vec![
+                Approval {
+                    operator: Addr::unchecked("operator"),
+                    expires: Expiration::Never {},
+                },
+                Approval {
+                    operator: Addr::unchecked("other"),
+                    expires: Expiration::Never {},
+                },
+            ]
+        );
+
+        let msg = QueryMsg::Approval {
+            token_id: String::from("1"),
+            operator: String::from("operator"),
+            include_expired: None,
+        };
+        let res: ApprovalResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.approval.expires, Expiration::Never {});
+
+        let msg = QueryMsg::Tokens {
+            owner: String::from("owner"),
+            start_after: None,
+            limit: None,
+        };
+        let res: TokensResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.tokens, vec!["1"]);
+
+        // Operator side of the operators has been built
+        assert!(OPERATOR_OWNERS.has(&deps.storage, (&operator, &owner)));
+
+        // Operator can still transfer the token
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env, mock_info("operator", &[]), msg).unwrap();
+        assert!(approvals_of(deps.as_ref(), 1).is_empty());
+    }
+
+    #[test]
+    fn migrate_from_v0_3() {
+        use crate::migrations::v0_3;
+        use crate::msg::{ApprovedOwnersResponse, ConfigResponse, OwnerApproval, QueryMsg};
+        use crate::query::query;
+        use cosmwasm_std::from_binary;
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // Store the state as it was laid out by v0.3.0, which had no paused
+        // targets and only kept the owner side of the operators
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.3.0").unwrap();
+        v0_3::CONFIG_V0_3
+            .save(&mut deps.storage, &old_config(0))
+            .unwrap();
+        assert!(query_config(deps.as_ref()).is_err());
+        let (owner, operator) = (Addr::unchecked("owner"), Addr::unchecked("market"));
+        OPERATORS
+            .save(
+                &mut deps.storage,
+                (&owner, &operator),
+                &Expiration::Never {},
+            )
+            .unwrap();
+
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        // Config loads again, with nothing paused
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.name, "TestNFT");
+        assert!(config.paused.is_empty());
+        let res: ConfigResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert!(res.paused.is_empty());
+
+        // Minting works as before
+        let info = mock_info("minter", &[]);
+        let msg = ExecuteMsg::Mint(mint_msg("owner".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = QueryMsg::ApprovedOwners {
+            operator: String::from("market"),
+            include_expired: None,
+            start_after: None,
+            limit: None,
+        };
+        let res: ApprovedOwnersResponse =
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(
+            res.owners,
+            vec![OwnerApproval {
+                owner: String::from("owner"),
+                expires: Expiration::Never {},
+            }]
+        );
+    }
+
+    #[test]
+    fn migrate_rejected() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // * different contract
+        set_contract_version(&mut deps.storage, "crates.io:cw721-base", "0.1.0").unwrap();
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+        match res {
+            ContractError::CannotMigrate { previous_contract } => {
+                assert_eq!(previous_contract, "crates.io:cw721-base")
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // * downgrade
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+        match res {
+            ContractError::CannotMigrateVersion { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * invalid version
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "latest").unwrap();
+        let res = migrate(deps.as_mut(), env, MigrateMsg {}).unwrap_err();
+        match res {
+            ContractError::SemVer(_) => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn english_auction() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let owner_info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let ends_at = Expiration::AtHeight(env.block.height + 100);
+
+        // Unsuccessful start
+        // * unauthorized sender
+        let start_msg = ExecuteMsg::StartAuction {
+            token_id: String::from("1"),
+            reserve_price: Coin::new(500, DENOM),
+            min_increment: Uint128::new(100),
+            ends_at,
+        };
+        let info = mock_info("other", &coins(0u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, start_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * auction without an end
+        let msg = ExecuteMsg::StartAuction {
+            token_id: String::from("1"),
+            reserve_price: Coin::new(500, DENOM),
+            min_increment: Uint128::new(100),
+            ends_at: Expiration::Never {},
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful start
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            owner_info.clone(),
+            start_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, res.attributes.len());
+
+        // * cannot be started twice
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), start_msg).unwrap_err();
+        match res {
+            ContractError::InAuction { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
+
+        // Token is locked during the auction
+        // * transfer
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::InAuction { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * approve
+        let msg = ExecuteMsg::Approve {
+            operator: String::from("operator"),
+            token_id: String::from("1"),
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::InAuction { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * buy
+        let info = mock_info("buyer", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::InAuction { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Unsuccessful bids
+        let bid_msg = ExecuteMsg::PlaceBid {
+            token_id: String::from("1"),
+        };
+        // * below reserve price
+        let info = mock_info("bidder1", &coins(499u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap_err();
+        match res {
+            ContractError::BidTooLow { min } => assert_eq!(min, Coin::new(500, DENOM)),
+            e => panic!("{:?}", e),
+        };
+
+        // * wrong denom
+        let info = mock_info("bidder1", &coins(500u128, "uatom"));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Payment(_) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * by the seller
+        let info = mock_info("creator", &coins(500u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful bids
+        let info = mock_info("bidder1", &coins(500u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(4, res.attributes.len());
+
+        // * below the minimum increment
+        let info = mock_info("bidder2", &coins(599u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap_err();
+        match res {
+            ContractError::BidTooLow { min } => assert_eq!(min, Coin::new(600, DENOM)),
+            e => panic!("{:?}", e),
+        };
+
+        // * outbid refunds the previous bidder
+        let info = mock_info("bidder2", &coins(600u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bidder1"),
+                amount: coins(500, DENOM.to_string()),
+            })
+        );
+
+        // Cannot cancel once there are bids
+        let msg = ExecuteMsg::CancelAuction {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Cannot settle before the end
+        let settle_msg = ExecuteMsg::SettleAuction {
+            token_id: String::from("1"),
+        };
+        let info = mock_info("anyone", &[]);
+        let res =
+            execute(deps.as_mut(), env.clone(), info.clone(), settle_msg.clone()).unwrap_err();
+        match res {
+            ContractError::AuctionNotEnded { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
+
+        // Bidding closes at the end
+        env.block.height += 100;
+        let info_bid = mock_info("bidder3", &coins(1000u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info_bid, bid_msg).unwrap_err();
+        match res {
+            ContractError::AuctionEnded { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
+
+        // Successful settlement
+        let res = execute(deps.as_mut(), env.clone(), info, settle_msg.clone()).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(600, DENOM.to_string()),
+            })
+        );
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("bidder2"));
+        assert!(!AUCTIONS.has(&deps.storage, 1));
+        assert!(!BIDS.has(&deps.storage, (1, 0)));
+        assert!(!BIDS.has(&deps.storage, (1, 1)));
+
+        // * token is free to move again
+        let info = mock_info("bidder2", &[]);
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("recipient"),
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // * cannot be settled twice
+        let res = execute(deps.as_mut(), env, info, settle_msg).unwrap_err();
+        match res {
+            ContractError::Std(StdError::NotFound { .. }) => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn auction_bid_limit() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::StartAuction {
+            token_id: String::from("1"),
+            reserve_price: Coin::new(1, DENOM),
+            min_increment: Uint128::zero(),
+            ends_at: Expiration::AtHeight(env.block.height + 100),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let bid_msg = ExecuteMsg::PlaceBid {
+            token_id: String::from("1"),
+        };
+        for amount in 1..=MAX_BIDS_PER_AUCTION as u128 {
+            let info = mock_info("bidder", &coins(amount, DENOM));
+            execute(deps.as_mut(), env.clone(), info, bid_msg.clone()).unwrap();
+        }
+
+        // * limit reached
+        let info = mock_info("bidder", &coins(1000, DENOM));
+        let res = execute(deps.as_mut(), env.clone(), info, bid_msg).unwrap_err();
+        match res {
+            ContractError::TooManyBids { max } => assert_eq!(max, MAX_BIDS_PER_AUCTION),
+            e => panic!("{:?}", e),
+        };
+
+        // Settlement removes the whole history
+        env.block.height += 100;
+        let msg = ExecuteMsg::SettleAuction {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+        let bids = BIDS
+            .prefix(1)
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(bids, 0);
+    }
+
+    #[test]
+    fn cancel_auction() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let owner_info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let start_msg = ExecuteMsg::StartAuction {
+            token_id: String::from("1"),
+            reserve_price: Coin::new(500, DENOM),
+            min_increment: Uint128::zero(),
+            ends_at: Expiration::AtHeight(env.block.height + 10),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            owner_info.clone(),
+            start_msg.clone(),
+        )
+        .unwrap();
+
+        // Unsuccessful cancel
+        // * unauthorized sender
+        let cancel_msg = ExecuteMsg::CancelAuction {
+            token_id: String::from("1"),
+        };
+        let info = mock_info("other", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, cancel_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful cancel
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), cancel_msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert!(!AUCTIONS.has(&deps.storage, 1));
+
+        // Settling an auction without bids leaves the token with its owner
+        execute(deps.as_mut(), env.clone(), owner_info, start_msg).unwrap();
+        env.block.height += 10;
+
+        let info = mock_info("anyone", &[]);
+        let msg = ExecuteMsg::SettleAuction {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("creator"));
+        assert!(!AUCTIONS.has(&deps.storage, 1));
+    }
+
+    #[test]
+    fn dutch_auction() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let owner_info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let start_time = env.block.time.plus_seconds(100);
+        let end_time = start_time.plus_seconds(1000);
+
+        // Unsuccessful start
+        // * unauthorized sender
+        let start_msg = ExecuteMsg::StartDutchAuction {
+            token_id: String::from("1"),
+            start_price: Coin::new(1500, DENOM),
+            floor_price: Uint128::new(500),
+            start_time,
+            end_time,
+            curve: DecayCurve::Linear,
+        };
+        let info = mock_info("other", &coins(0u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, start_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * floor above the start price
+        let msg = ExecuteMsg::StartDutchAuction {
+            token_id: String::from("1"),
+            start_price: Coin::new(500, DENOM),
+            floor_price: Uint128::new(1500),
+            start_time,
+            end_time,
+            curve: DecayCurve::Linear,
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * ends before it starts
+        let msg = ExecuteMsg::StartDutchAuction {
+            token_id: String::from("1"),
+            start_price: Coin::new(1500, DENOM),
+            floor_price: Uint128::new(500),
+            start_time: end_time,
+            end_time: start_time,
+            curve: DecayCurve::Linear,
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful start
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), start_msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let auction = DUTCH_AUCTIONS.load(&deps.storage, 1).unwrap();
+        assert_eq!(auction.token_id, "1");
+
+        // Cannot be bought before it starts
+        let buyer_info = mock_info("buyer", &coins(1500u128, DENOM.to_string()));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer_info.clone(),
+            ExecuteMsg::Buy {
+                token_id: String::from("1"),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::NotListed { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
+
+        // Halfway through, the price is halfway down to the floor
+        env.block.time = start_time.plus_seconds(500);
+        assert_eq!(auction.price_at(env.block.time), Coin::new(1000, DENOM));
+
+        // * paying below the current price
+        let info = mock_info("buyer", &coins(900u128, DENOM.to_string()));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::Buy {
+                token_id: String::from("1"),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidAmount { val, .. } => assert_eq!(val, Coin::new(1000, DENOM)),
+            e => panic!("{:?}", e),
+        };
+
+        // Successful buy, overpayment is refunded
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer_info,
+            ExecuteMsg::Buy {
+                token_id: String::from("1"),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("buyer"),
+                amount: coins(500, DENOM),
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(1000, DENOM),
+            })
+        );
+
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("buyer"));
+        assert!(!DUTCH_AUCTIONS.has(&deps.storage, 1));
+    }
+
+    #[test]
+    fn dutch_auction_stepwise_and_delist() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let owner_info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let start_time = env.block.time;
+        let msg = ExecuteMsg::StartDutchAuction {
+            token_id: String::from("1"),
+            start_price: Coin::new(1500, DENOM),
+            floor_price: Uint128::new(500),
+            start_time,
+            end_time: start_time.plus_seconds(1000),
+            curve: DecayCurve::Stepwise { interval: 300 },
+        };
+        execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap();
+
+        // Price only drops at the end of every step, and stays at the floor
+        let auction = DUTCH_AUCTIONS.load(&deps.storage, 1).unwrap();
+        let price = |secs| auction.price_at(start_time.plus_seconds(secs)).amount;
+        assert_eq!(price(0), Uint128::new(1500));
+        assert_eq!(price(299), Uint128::new(1500));
+        assert_eq!(price(300), Uint128::new(1200));
+        assert_eq!(price(999), Uint128::new(600));
+        assert_eq!(price(1000), Uint128::new(500));
+        assert_eq!(price(5000), Uint128::new(500));
+
+        // Delisting ends the auction
+        let msg = ExecuteMsg::Delist {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), owner_info, msg).unwrap();
+        assert!(!DUTCH_AUCTIONS.has(&deps.storage, 1));
+
+        let buyer_info = mock_info("buyer", &coins(1500u128, DENOM.to_string()));
+        let res = execute(
+            deps.as_mut(),
+            env,
+            buyer_info,
+            ExecuteMsg::Buy {
+                token_id: String::from("1"),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::NotListed { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn standing_offers() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let offer_msg = ExecuteMsg::MakeOffer {
+            token_id: String::from("1"),
+            expires: Some(Expiration::AtHeight(env.block.height + 100)),
+        };
+
+        // Unsuccessful offer
+        // * owner offering on their own token
+        let info = mock_info("creator", &coins(500u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg.clone()).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * no funds
+        let info = mock_info("bidder1", &coins(0u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Payment(PaymentError::NoFunds {}) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * already expired
+        let info = mock_info("bidder1", &coins(500u128, DENOM.to_string()));
+        let msg = ExecuteMsg::MakeOffer {
+            token_id: String::from("1"),
+            expires: Some(Expiration::AtHeight(env.block.height)),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful offers
+        let info = mock_info("bidder1", &coins(500u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg.clone()).unwrap();
+        assert_eq!(0, res.messages.len());
+        let info = mock_info("bidder2", &coins(600u128, DENOM.to_string()));
+        execute(deps.as_mut(), env.clone(), info, offer_msg.clone()).unwrap();
+
+        // * a new offer replaces the previous one, which is refunded
+        let info = mock_info("bidder1", &coins(700u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bidder1"),
+                amount: coins(500, DENOM),
+            })
+        );
+        let offer = offers()
+            .load(&deps.storage, (1, &Addr::unchecked("bidder1")))
+            .unwrap();
+        assert_eq!(offer.amount, coins(700, DENOM));
+
+        // Offers stay valid after a transfer
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("new_owner"),
+            token_id: String::from("1"),
+        };
+        let info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Unsuccessful accept
+        // * previous owner
+        let accept_msg = ExecuteMsg::AcceptOffer {
+            token_id: String::from("1"),
+            bidder: String::from("bidder1"),
+        };
+        let info = mock_info("creator", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, accept_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * no such offer
+        let msg = ExecuteMsg::AcceptOffer {
+            token_id: String::from("1"),
+            bidder: String::from("bidder3"),
+        };
+        let owner_info = mock_info("new_owner", &[]);
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::OfferNotFound { token_id, bidder } => {
+                assert_eq!(token_id, 1);
+                assert_eq!(bidder, "bidder3");
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // Successful accept by the new owner
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), accept_msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("new_owner"),
+                amount: coins(700, DENOM),
+            })
+        );
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("bidder1"));
+        let offer = offers()
+            .may_load(&deps.storage, (1, &Addr::unchecked("bidder1")))
+            .unwrap();
+        assert_eq!(offer, None);
+
+        // Expired offers can no longer be accepted, only refunded
+        env.block.height += 100;
+        let msg = ExecuteMsg::AcceptOffer {
+            token_id: String::from("1"),
+            bidder: String::from("bidder2"),
+        };
+        let info = mock_info("bidder1", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::OfferExpired { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        let msg = ExecuteMsg::CancelOffer {
+            token_id: String::from("1"),
+        };
+        let info = mock_info("bidder2", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bidder2"),
+                amount: coins(600, DENOM),
+            })
+        );
+
+        // * nothing left to cancel
+        let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        match res {
+            ContractError::OfferNotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn collection_offer_fills() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for owner in ["creator", "creator", "bidder"] {
+            let msg = ExecuteMsg::Mint(mint_msg(owner.to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let offer_msg = ExecuteMsg::MakeCollectionOffer {
+            price: Coin::new(300, DENOM),
+            quantity: 2,
+            expires: Some(Expiration::AtHeight(env.block.height + 100)),
+        };
+
+        // Unsuccessful offer
+        // * funds not covering every token
+        let info = mock_info("bidder", &coins(300u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg.clone()).unwrap_err();
+        match res {
+            ContractError::InvalidAmount { val, funds } => {
+                assert_eq!(val, Coin::new(600, DENOM));
+                assert_eq!(funds, Coin::new(300, DENOM));
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // * zero quantity
+        let msg = ExecuteMsg::MakeCollectionOffer {
+            price: Coin::new(300, DENOM),
+            quantity: 0,
+            expires: None,
+        };
+        let info = mock_info("bidder", &coins(300u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful offer
+        let info = mock_info("bidder", &coins(600u128, DENOM.to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info, offer_msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(
+            query_config(deps.as_ref())
+                .unwrap()
+                .next_collection_offer_id,
+            2
+        );
+
+        // Unsuccessful accept
+        // * not the owner
+        let accept_msg = ExecuteMsg::AcceptCollectionOffer {
+            token_id: String::from("1"),
+            offer_id: 1,
+        };
+        let info = mock_info("other", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, accept_msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * bidder already owns the token
+        let msg = ExecuteMsg::AcceptCollectionOffer {
+            token_id: String::from("3"),
+            offer_id: 1,
+        };
+        let info = mock_info("bidder", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful accept fills one token of the offer
+        let owner_info = mock_info("creator", &[]);
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), accept_msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(300, DENOM),
+            })
+        );
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("bidder"));
+        let offer = collection_offers().load(&deps.storage, 1).unwrap();
+        assert_eq!(offer.quantity, 1);
+
+        // * expired offers cannot be filled
+        env.block.height += 100;
+        let msg = ExecuteMsg::AcceptCollectionOffer {
+            token_id: String::from("2"),
+            offer_id: 1,
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CollectionOfferExpired { offer_id } => assert_eq!(offer_id, 1),
+            e => panic!("{:?}", e),
+        };
+
+        // Only the bidder can cancel, getting back the unfilled part
+        let msg = ExecuteMsg::CancelCollectionOffer { offer_id: 1 };
+        let res = execute(deps.as_mut(), env.clone(), owner_info, msg.clone()).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        let info = mock_info("bidder", &[]);
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("bidder"),
+                amount: coins(300, DENOM),
+            })
+        );
+        assert!(collection_offers()
+            .may_load(&deps.storage, 1)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn cw20_buy() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Token priced only in CW20 tokens
+        let mut mint = mint_msg("creator".to_string());
+        mint.price = vec![];
+        mint.cw20_price = Some(Cw20Coin {
+            address: String::from("community_token"),
+            amount: Uint128::new(1000),
+        });
+        mint.royalty = Some(Royalty {
+            payment_address: String::from("artist"),
+            share: Decimal::percent(10),
+        });
+        execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Mint(mint)).unwrap();
+
+        let buy_msg = |cw20: &str, amount: u128| {
+            let info = mock_info(cw20, &[]);
+            let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: String::from("buyer"),
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::Buy {
+                    token_id: String::from("1"),
+                })
+                .unwrap(),
+            });
+            (info, msg)
+        };
+
+        // Unsuccessful purchase
+        // * native funds
+        let info = mock_info("buyer", &coins(1000u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * insufficient amount
+        let (info, msg) = buy_msg("community_token", 999);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::InvalidCw20Amount { val, funds } => {
+                assert_eq!(val.amount, Uint128::new(1000));
+                assert_eq!(funds.amount, Uint128::new(999));
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // * another CW20 token
+        let (info, msg) = buy_msg("other_token", 1000);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        match res {
+            ContractError::InvalidCw20Amount { funds, .. } => {
+                assert_eq!(funds.address, Addr::unchecked("other_token"));
+            }
+            e => panic!("{:?}", e),
+        };
+
+        // Successful purchase, paid out with CW20 transfers
+        let (info, msg) = buy_msg("community_token", 1000);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(2, res.messages.len());
+        let transfers = [("artist", 100u128), ("creator", 900)];
+        for (sub_msg, (recipient, amount)) in res.messages.iter().zip(transfers) {
+            assert_eq!(
+                sub_msg.msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("community_token"),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: String::from(recipient),
+                        amount: Uint128::new(amount),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("buyer"));
+        assert!(!token.listed);
+
+        // Setting a native price replaces the CW20 price
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("1"),
+            price: coins(500, DENOM),
+            price_mode: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), msg).unwrap();
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.cw20_price, None);
+
+        // and the other way around
+        let msg = ExecuteMsg::SetCw20Price {
+            token_id: String::from("1"),
+            price: Cw20Coin {
+                address: String::from("community_token"),
+                amount: Uint128::new(2000),
+            },
+        };
+        execute(deps.as_mut(), env, mock_info("buyer", &[]), msg).unwrap();
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.cw20_price.unwrap().amount, Uint128::new(2000));
+        assert!(token.listed);
+    }
+
+    #[test]
+    fn price_modes() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Unsuccessful mint
+        // * duplicate denom
+        let mut mint = mint_msg("creator".to_string());
+        mint.price = vec![Coin::new(1000, DENOM), Coin::new(500, DENOM)];
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Mint(mint),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Token 1 requires both coins, token 2 either of them
+        let price = vec![Coin::new(1000, DENOM), Coin::new(50, "uatom")];
+        for price_mode in [None, Some(PriceMode::AnyOf)] {
+            let mut mint = mint_msg("creator".to_string());
+            mint.price = price.clone();
+            mint.price_mode = price_mode;
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::Mint(mint),
+            )
+            .unwrap();
+        }
+        let buy = |token_id: &str| ExecuteMsg::Buy {
+            token_id: String::from(token_id),
+        };
+
+        // AllOf
+        // * one of the coins is missing
+        let info = mock_info("buyer", &coins(1000, DENOM));
+        let res = execute(deps.as_mut(), env.clone(), info, buy("1")).unwrap_err();
+        match res {
+            ContractError::MissingDenom { denom } => assert_eq!(denom, "uatom"),
+            e => panic!("{:?}", e),
+        };
+
+        // * one of the coins is short
+        let funds = vec![Coin::new(1000, DENOM), Coin::new(40, "uatom")];
+        let info = mock_info("buyer", &funds);
+        let res = execute(deps.as_mut(), env.clone(), info, buy("1")).unwrap_err();
+        match res {
+            ContractError::InvalidAmount { val, funds } => {
+                assert_eq!(val, Coin::new(50, "uatom"));
+                assert_eq!(funds, Coin::new(40, "uatom"));
+            }
+            e => panic!("{:?}", e),
+        };
+
+        let info = mock_info("buyer", &price);
+        let res = execute(deps.as_mut(), env.clone(), info, buy("1")).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: price.clone(),
+            })
+        );
+
+        // AnyOf
+        // * paying with both coins
+        let info = mock_info("buyer", &price);
+        let res = execute(deps.as_mut(), env.clone(), info, buy("2")).unwrap_err();
+        match res {
+            ContractError::Payment(PaymentError::MultipleDenoms {}) => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * short amount
+        let info = mock_info("buyer", &coins(49, "uatom"));
+        let res = execute(deps.as_mut(), env.clone(), info, buy("2")).unwrap_err();
+        match res {
+            ContractError::InvalidAmount { val, .. } => assert_eq!(val, Coin::new(50, "uatom")),
+            e => panic!("{:?}", e),
+        };
+
+        // Only the coin paid goes to the seller
+        let info = mock_info("buyer", &coins(50, "uatom"));
+        let res = execute(deps.as_mut(), env.clone(), info, buy("2")).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("creator"),
+                amount: coins(50, "uatom"),
+            })
+        );
+
+        // SetPrice keeps the mode unless a new one is given
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("2"),
+            price: price.clone(),
+            price_mode: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), msg).unwrap();
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.price_mode, PriceMode::AnyOf);
+
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("2"),
+            price,
+            price_mode: Some(PriceMode::AllOf),
+        };
+        execute(deps.as_mut(), env, mock_info("buyer", &[]), msg).unwrap();
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.price_mode, PriceMode::AllOf);
+    }
+
+    #[test]
+    fn platform_fee() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("creator".to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let fee = PlatformFee {
+            bps: 250,
+            recipients: vec![
+                FeeRecipient {
+                    address: String::from("treasury"),
+                    weight: 3,
+                },
+                FeeRecipient {
+                    address: String::from("ops"),
+                    weight: 1,
+                },
+            ],
+        };
+
+        // Unsuccessful update
+        // * sender is not the admin
+        let msg = ExecuteMsg::UpdatePlatformFee {
+            platform_fee: Some(fee.clone()),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * fee above the maximum
+        let mut high_fee = fee.clone();
+        high_fee.bps = MAX_PLATFORM_FEE_BPS + 1;
+        let msg = ExecuteMsg::UpdatePlatformFee {
+            platform_fee: Some(high_fee),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::FeeTooHigh { max_bps } => assert_eq!(max_bps, MAX_PLATFORM_FEE_BPS),
+            e => panic!("{:?}", e),
+        };
+
+        // * no recipients
+        let msg = ExecuteMsg::UpdatePlatformFee {
+            platform_fee: Some(PlatformFee {
+                bps: 250,
+                recipients: vec![],
+            }),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful update
+        let msg = ExecuteMsg::UpdatePlatformFee {
+            platform_fee: Some(fee.clone()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(query_config(deps.as_ref()).unwrap().platform_fee, Some(fee));
+
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: None,
+            royalty: Some(Royalty {
+                payment_address: String::from("artist"),
+                share: Decimal::percent(10),
+            }),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Fixed price sale: 25 fee split 3:1, 100 royalty, rest to the seller
+        let expected = [
+            ("treasury", 19u128),
+            ("ops", 6),
+            ("artist", 100),
+            ("creator", 875),
+        ];
+        let assert_payouts = |res: Response| {
+            assert_eq!(res.messages.len(), expected.len());
+            for (sub_msg, (to, amount)) in res.messages.iter().zip(expected) {
+                assert_eq!(
+                    sub_msg.msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: String::from(to),
+                        amount: coins(amount, DENOM),
+                    })
+                );
+            }
+        };
+
+        let info = mock_info("buyer", &coins(1000, DENOM));
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        assert_payouts(execute(deps.as_mut(), env.clone(), info, msg).unwrap());
+
+        // Accepted offer
+        let info = mock_info("bidder", &coins(1000, DENOM));
+        let msg = ExecuteMsg::MakeOffer {
+            token_id: String::from("2"),
+            expires: None,
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::AcceptOffer {
+            token_id: String::from("2"),
+            bidder: String::from("bidder"),
+        };
+        assert_payouts(execute(deps.as_mut(), env, mock_info("creator", &[]), msg).unwrap());
+    }
+
+    #[test]
+    fn public_mint() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("admin", &[]);
+        let mut msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        msg.max_supply = Some(4);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Reserved mint by the minter
+        let msg = ExecuteMsg::Mint(mint_msg("admin".to_string()));
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let public_msg = ExecuteMsg::PublicMint {};
+        let pay = coins(100, DENOM);
+
+        // * public mint not configured yet
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            public_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PublicMintClosed => {}
+            e => panic!("{:?}", e),
+        };
+
+        let start_time = env.block.time.plus_seconds(100);
+        let config = PublicMintConfig {
+            price: pay.clone(),
+            cw20_price: None,
+            per_address_limit: 2,
+            start_time,
+            end_time: start_time.plus_seconds(1000),
+            base_token_uri: Some(String::from("ipfs://collection/")),
+        };
+
+        // Unsuccessful update
+        // * sender is not the admin
+        let msg = ExecuteMsg::UpdatePublicMint {
+            public_mint: Some(config.clone()),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful update
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // * not started yet
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            public_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PublicMintClosed => {}
+            e => panic!("{:?}", e),
+        };
+
+        env.block.time = start_time;
+
+        // * wrong payment
+        let info_short = mock_info("alice", &coins(99, DENOM));
+        let res = execute(deps.as_mut(), env.clone(), info_short, public_msg.clone()).unwrap_err();
+        match res {
+            ContractError::InvalidAmount { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful public mints
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            public_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(0, res.messages.len());
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("alice"));
+        assert_eq!(token.token_uri, Some(String::from("ipfs://collection/2")));
+        assert!(token.listed);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            public_msg.clone(),
+        )
+        .unwrap();
+
+        // * per address limit
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            public_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::MintLimitReached { limit } => assert_eq!(limit, 2),
+            e => panic!("{:?}", e),
+        };
+        assert_eq!(
+            PUBLIC_MINTS
+                .load(&deps.storage, &Addr::unchecked("alice"))
+                .unwrap(),
+            2
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &pay),
+            public_msg.clone(),
+        )
+        .unwrap();
+
+        // * max supply, for public and reserved mints alike
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &pay),
+            public_msg,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::MaxSupplyReached { max_supply } => assert_eq!(max_supply, 4),
+            e => panic!("{:?}", e),
+        };
+        let msg = ExecuteMsg::Mint(mint_msg("admin".to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::MaxSupplyReached { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Withdraw
+        // * sender is not the admin
+        let msg = ExecuteMsg::WithdrawMintProceeds { recipient: None };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("admin"),
+                amount: coins(300, DENOM),
+            })
+        );
+
+        // * nothing left
+        let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn public_mint_after_renounce() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let info = mock_info("admin", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let pay = coins(100, DENOM);
+        let config = PublicMintConfig {
+            price: pay.clone(),
+            cw20_price: None,
+            per_address_limit: 2,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(1000),
+            base_token_uri: None,
+        };
+        let msg = ExecuteMsg::UpdatePublicMint {
+            public_mint: Some(config),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+        let msg_renounce = ExecuteMsg::RenounceMinter {};
+        execute(deps.as_mut(), env.clone(), info.clone(), msg_renounce).unwrap();
+
+        // * open phase is closed by the renounce
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            ExecuteMsg::PublicMint {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PublicMintClosed => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * cannot be reopened
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::PublicMintClosed => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Removing the phase is still allowed
+        let msg = ExecuteMsg::UpdatePublicMint { public_mint: None };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(query_config(deps.as_ref()).unwrap().token_count, 0);
+    }
+
+    fn allowlist_leaf(address: &str, allocation: u32) -> [u8; 32] {
+        Sha256::digest(format!("{}:{}", address, allocation).as_bytes()).into()
+    }
+
+    fn allowlist_parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let mut pair = [a, b];
+        pair.sort_unstable();
+        Sha256::digest(pair.concat()).into()
+    }
+
+    #[test]
+    fn allowlist_mint() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let info = mock_info("admin", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Tree of two leaves, each being the proof of the other
+        let alice = allowlist_leaf("alice", 2);
+        let bob = allowlist_leaf("bob", 1);
+        assert_eq!(
+            hex::encode(bob),
+            "ad34a78e6cbd267ecd64b95316b8dfe84acddcb9a5386d78282c129e3307e8ce"
+        );
+        let root = hex::encode(allowlist_parent(alice, bob));
+        let alice_msg = ExecuteMsg::MintAllowlisted {
+            proof: vec![hex::encode(bob)],
+            allocation: 2,
+        };
+        let pay = coins(50, DENOM);
+
+        // * allowlist not configured yet
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            alice_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::AllowlistMintClosed => {}
+            e => panic!("{:?}", e),
+        };
+
+        let mut config = AllowlistConfig {
+            merkle_root: String::from("not a root"),
+            price: pay.clone(),
+            cw20_price: None,
+            per_address_limit: 5,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(1000),
+            base_token_uri: None,
+        };
+
+        // Unsuccessful update
+        // * sender is not the admin
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(config.clone()),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * root is not a sha256 hash
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(config.clone()),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
         match res {
             ContractError::CustomError { .. } => {}
             e => panic!("{:?}", e),
-        }
-    }
-
-    #[test]
-    fn mint() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
+        };
+
+        // Successful update
+        config.merkle_root = root;
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(config),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // * allocation not in the tree
+        let msg = ExecuteMsg::MintAllowlisted {
+            proof: vec![hex::encode(bob)],
+            allocation: 3,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &pay), msg).unwrap_err();
+        match res {
+            ContractError::InvalidProof => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * sender not in the tree
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("carol", &pay),
+            alice_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidProof => {}
+            e => panic!("{:?}", e),
+        };
 
-        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
-        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // * wrong payment
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(49, DENOM)),
+            alice_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidAmount { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
-        // Successful token minting
-        let mint_msg = mint_msg("creator".to_string());
-        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), mint_msg).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(4, res.attributes.len());
+        // Successful allowlist mints
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("alice", &pay),
+                alice_msg.clone(),
+            )
+            .unwrap();
+        }
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("alice"));
+        assert_eq!(token.base_price, pay);
+        assert!(token.listed);
 
-        let stored_token = query_tokens(deps.as_ref(), 1).unwrap();
-        assert_eq!(stored_token.owner, "creator");
-        assert_eq!(stored_token.token_id, 1);
-        assert_eq!(stored_token.base_price, coins(1000, DENOM.to_string()));
-        assert_eq!(stored_token.approvals, vec![]);
-        assert_eq!(stored_token.token_uri, None);
+        // * allocation used up
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            alice_msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::MintLimitReached { limit } => assert_eq!(limit, 2),
+            e => panic!("{:?}", e),
+        };
 
-        // Unsuccessful token minting
-        // * owner name is empty
-        let msg = MintMsg {
-            owner: String::new(),
-            token_uri: None,
-            price: coins(1000, DENOM.to_string()),
+        // Swap the root for a tree where only bob is left
+        // * sender is not the admin
+        let msg = ExecuteMsg::UpdateMerkleRoot {
+            merkle_root: hex::encode(bob),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
         };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        // * old proof is no longer valid
+        let msg = ExecuteMsg::MintAllowlisted {
+            proof: vec![hex::encode(alice)],
+            allocation: 1,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &pay), msg).unwrap_err();
         match res {
-            ContractError::Std(StdError::GenericErr { .. }) => {}
+            ContractError::InvalidProof => {}
             e => panic!("{:?}", e),
         };
 
-        // * amount is empty i.e., price has been provided as 0Denom
-        let msg = MintMsg {
-            owner: String::from("owner"),
-            token_uri: None,
-            price: coins(0, DENOM.to_string()),
+        let msg = ExecuteMsg::MintAllowlisted {
+            proof: vec![],
+            allocation: 1,
         };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &pay),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            MINT_PROCEEDS.load(&deps.storage, DENOM).unwrap(),
+            Uint128::new(150)
+        );
 
-        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        // Renouncing the minter closes the allowlist mint for good
+        let admin = mock_info("admin", &[]);
+        let renounce = ExecuteMsg::RenounceMinter {};
+        execute(deps.as_mut(), env.clone(), admin.clone(), renounce).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &pay),
+            alice_msg,
+        )
+        .unwrap_err();
         match res {
-            ContractError::CustomError { .. } => {}
-            e => panic! {"{:?}", e},
+            ContractError::AllowlistMintClosed => {}
+            e => panic!("{:?}", e),
         };
 
-        // * price is empty
-        let msg = MintMsg {
-            owner: String::from("owner"),
-            token_uri: None,
-            price: vec![],
+        // * cannot be reopened
+        let allowlist = query_config(deps.as_ref()).unwrap().allowlist;
+        let update = ExecuteMsg::UpdateAllowlist { allowlist };
+        let res = execute(deps.as_mut(), env.clone(), admin, update).unwrap_err();
+        match res {
+            ContractError::AllowlistMintClosed => {}
+            e => panic!("{:?}", e),
         };
 
-        let res = handle_mint(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        // * window closed
+        env.block.time = env.block.time.plus_seconds(1000);
+        let res = execute(deps.as_mut(), env, mock_info("bob", &pay), msg).unwrap_err();
         match res {
-            ContractError::CustomError { .. } => {}
-            e => panic! {"{:?}", e},
+            ContractError::AllowlistMintClosed => {}
+            e => panic!("{:?}", e),
         };
     }
 
     #[test]
-    fn approve() {
+    fn cw20_mint() {
         let mut deps = mock_dependencies();
-        let mut env = mock_env();
-        env.block.height = 50u64;
-        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+        let env = mock_env();
 
+        let info = mock_info("admin", &[]);
         let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-
-        let mint_msg = mint_msg("owner1".to_string());
-        handle_mint(deps.as_mut(), env.clone(), info, mint_msg).unwrap();
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        // Successful approval request
-        // * by owner
-        let approve_msg = ExecuteMsg::Approve {
-            operator: "operator".to_string(),
-            token_id: 1u64,
-            expires: None,
+        let cw20_price = Cw20Coin {
+            address: String::from("cw20token"),
+            amount: Uint128::new(100),
+        };
+        let public_mint = PublicMintConfig {
+            price: vec![],
+            cw20_price: Some(cw20_price.clone()),
+            per_address_limit: 1,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(1000),
+            base_token_uri: None,
         };
 
-        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 4);
-
-        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        assert_eq!(token.approvals[0].operator, Addr::unchecked("operator"));
-        assert_eq!(token.approvals[0].expires, Expiration::Never {});
+        // Unsuccessful update
+        // * zero CW20 price
+        let mut config = public_mint.clone();
+        config.cw20_price = Some(Cw20Coin {
+            address: String::from("cw20token"),
+            amount: Uint128::zero(),
+        });
+        let msg = ExecuteMsg::UpdatePublicMint {
+            public_mint: Some(config),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
-        // * by operator
+        // Successful update, without a native price
+        let msg = ExecuteMsg::UpdatePublicMint {
+            public_mint: Some(public_mint),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let mint_msg = |cw20: &str, sender: &str, amount: u128, msg: &ReceiveMsg| {
+            let info = mock_info(cw20, &[]);
+            let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: String::from(sender),
+                amount: Uint128::new(amount),
+                msg: to_binary(msg).unwrap(),
+            });
+            (info, msg)
+        };
 
-        // Approve all for operator1
-        let res = handle_approve_all(
+        // Unsuccessful public mints
+        // * native funds
+        let res = execute(
             deps.as_mut(),
             env.clone(),
-            info.clone(),
-            String::from("operator1"),
-            None,
+            mock_info("alice", &coins(100, DENOM)),
+            ExecuteMsg::PublicMint {},
         )
-        .unwrap();
-        assert_eq!(res.messages.len(), 0);
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
-        // operator1 approves user1
-        let info = mock_info("operator1", &coins(0, &DENOM.to_string()));
-        let res = handle_approve(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            "user1",
-            1u64,
-            None,
-        )
-        .unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 4);
-        assert_eq!(res.attributes[1].value, String::from("operator1"));
+        // * wrong amount
+        let (info_mint, msg) = mint_msg("cw20token", "alice", 99, &ReceiveMsg::PublicMint {});
+        let res = execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap_err();
+        match res {
+            ContractError::InvalidCw20Amount { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // * wrong CW20 token
+        let (info_mint, msg) = mint_msg("othertoken", "alice", 100, &ReceiveMsg::PublicMint {});
+        let res = execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap_err();
+        match res {
+            ContractError::InvalidCw20Amount { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
+        // Successful public mint, to the sender of the CW20 tokens
+        let (info_mint, msg) = mint_msg("cw20token", "alice", 100, &ReceiveMsg::PublicMint {});
+        execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap();
         let token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        assert_eq!(token.approvals.len(), 2);
-        assert_eq!(token.approvals[1].operator, Addr::unchecked("user1"));
+        assert_eq!(token.owner, Addr::unchecked("alice"));
+        assert!(token.listed);
+        assert_eq!(
+            token.cw20_price,
+            Some(Cw20CoinVerified {
+                address: Addr::unchecked("cw20token"),
+                amount: Uint128::new(100),
+            })
+        );
 
-        // Unsuccessful approval request
-        // * empty operator field
-        let approve_msg = ExecuteMsg::Approve {
-            operator: String::new(),
-            token_id: 1u64,
-            expires: None,
-        };
-        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+        // * per address limit still applies
+        let (info_mint, msg) = mint_msg("cw20token", "alice", 100, &ReceiveMsg::PublicMint {});
+        let res = execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap_err();
         match res {
-            ContractError::Std(StdError::GenericErr { .. }) => {}
+            ContractError::MintLimitReached { limit } => assert_eq!(limit, 1),
             e => panic!("{:?}", e),
         };
 
-        // * Invalid token
-        let approve_msg = ExecuteMsg::Approve {
-            operator: "operator".to_string(),
-            token_id: 2u64,
-            expires: None,
+        // Allowlist priced in native coins cannot be paid in CW20 tokens
+        let root = hex::encode(allowlist_leaf("bob", 1));
+        let mut allowlist = AllowlistConfig {
+            merkle_root: root,
+            price: coins(50, DENOM),
+            cw20_price: None,
+            per_address_limit: 1,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(1000),
+            base_token_uri: None,
         };
-        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
-        execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(allowlist.clone()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        // * expired approval
-        let approve_msg = ExecuteMsg::Approve {
-            operator: "operator".to_string(),
-            token_id: 1u64,
-            expires: Some(Expiration::AtHeight(45u64)),
+        let allowlist_msg = ReceiveMsg::MintAllowlisted {
+            proof: vec![],
+            allocation: 1,
         };
-        let info = mock_info("owner1", &coins(0, &DENOM.to_string()));
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap_err();
+        let (info_mint, msg) = mint_msg("cw20token", "bob", 100, &allowlist_msg);
+        let res = execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap_err();
         match res {
-            ContractError::Expired {} => {}
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
+
+        // Successful allowlist mint once priced in the CW20 token
+        allowlist.cw20_price = Some(cw20_price);
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(allowlist),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let (info_mint, msg) = mint_msg("cw20token", "bob", 100, &allowlist_msg);
+        execute(deps.as_mut(), env.clone(), info_mint, msg).unwrap();
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("bob"));
+
+        // Withdraw the CW20 proceeds
+        let msg = ExecuteMsg::WithdrawMintProceeds { recipient: None };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(1, res.messages.len());
+        let expected = Cw20Contract(Addr::unchecked("cw20token"))
+            .call(Cw20ExecuteMsg::Transfer {
+                recipient: String::from("admin"),
+                amount: Uint128::new(200),
+            })
+            .unwrap();
+        assert_eq!(res.messages[0].msg, expected);
+
+        // * nothing left
+        let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
             e => panic!("{:?}", e),
         };
     }
 
     #[test]
-    fn approve_all() {
+    fn batch_messages() {
         let mut deps = mock_dependencies();
-        let mut env = mock_env();
-        env.block.height = 25u64;
-        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
+        let env = mock_env();
+
+        let info = mock_info("minter", &[]);
         let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        // Successful approval for operator
-        // * new operator
-        let operator = String::from("operator");
-        let expires = None;
-
-        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
-            .unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 3);
-
-        let res = OPERATORS
-            .load(&deps.storage, (&info.sender, &Addr::unchecked("operator")))
-            .unwrap();
-        assert_eq!(res, Expiration::Never {});
-
-        // * update expiration
-        let operator = String::from("operator");
-        let expires = Some(Expiration::AtHeight(130_000));
-
-        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
-            .unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 3);
+        // Successful batch mint
+        let msgs = vec![
+            mint_msg("alice".to_string()),
+            mint_msg("alice".to_string()),
+            mint_msg("bob".to_string()),
+        ];
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::BatchMint(msgs),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0], attr("action", "batch_mint"));
+        assert_eq!(res.attributes[2], attr("count", "3"));
+        let minted: Vec<_> = res
+            .attributes
+            .iter()
+            .filter(|val| val.key == "token_id")
+            .map(|val| val.value.as_str())
+            .collect();
+        assert_eq!(minted, vec!["1", "2", "3"]);
+        assert_eq!(query_config(deps.as_ref()).unwrap().token_count, 3);
+
+        // Unsuccessful batch mint
+        // * one of the tokens has an invalid price, nothing is minted
+        let mut bad = mint_msg("alice".to_string());
+        bad.price = vec![];
+        let msgs = vec![bad, mint_msg("alice".to_string())];
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::BatchMint(msgs),
+        )
+        .unwrap_err();
+        assert_eq!(query_config(deps.as_ref()).unwrap().token_count, 3);
 
-        // Unsuccessful approval for operator
-        // * Operator exists
-        let operator = String::from("operator");
-        let expires = Some(Expiration::AtHeight(130_000));
-        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
-            .unwrap_err();
+        // * empty batch
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::BatchMint(vec![]),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
+        // Update max batch size
+        // * sender is not the admin
+        let msg = ExecuteMsg::UpdateMaxBatchSize { max_batch_size: 2 };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
         match res {
-            ContractError::OperatorApproved { .. } => {}
+            ContractError::Unauthorized => {}
             e => panic!("{:?}", e),
         };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        // * Expired height
-        let operator = String::from("operator");
-        let expires = Some(Expiration::AtHeight(24u64));
+        // * batch too large
+        let msg = ExecuteMsg::BatchApprove {
+            operator: String::from("carol"),
+            token_ids: vec![String::from("1"), String::from("2"), String::from("3")],
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
+        match res {
+            ContractError::BatchTooLarge { max_batch_size } => assert_eq!(max_batch_size, 2),
+            e => panic!("{:?}", e),
+        };
 
-        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
-            .unwrap_err();
+        // * alice does not own token 3, nothing is approved
+        let msg = ExecuteMsg::BatchApprove {
+            operator: String::from("carol"),
+            token_ids: vec![String::from("3"), String::from("1")],
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
         match res {
-            ContractError::Expired => {}
+            ContractError::Unauthorized => {}
             e => panic!("{:?}", e),
         };
+        assert!(approvals_of(deps.as_ref(), 1).is_empty());
 
-        // * Expired time
-        let operator = String::from("operator");
-        let expires = Some(Expiration::AtTime(env.block.time.minus_seconds(64u64)));
+        // Successful batch approve
+        let msg = ExecuteMsg::BatchApprove {
+            operator: String::from("carol"),
+            token_ids: vec![String::from("1"), String::from("2")],
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
+        assert_eq!(res.attributes[0], attr("action", "batch_approve"));
+        for token_id in [1u64, 2] {
+            let approvals = approvals_of(deps.as_ref(), token_id);
+            assert_eq!(approvals[0].operator, Addr::unchecked("carol"));
+        }
 
-        let res = handle_approve_all(deps.as_mut(), env.clone(), info.clone(), operator, expires)
-            .unwrap_err();
+        // Unsuccessful batch transfer
+        // * carol is not approved for token 3, nothing is transferred
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![
+                (String::from("dave"), String::from("3")),
+                (String::from("dave"), String::from("1")),
+            ],
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), msg).unwrap_err();
         match res {
-            ContractError::Expired => {}
+            ContractError::Unauthorized => {}
             e => panic!("{:?}", e),
         };
+        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("alice"));
+
+        // Successful batch transfer by the approved operator
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![
+                (String::from("dave"), String::from("1")),
+                (String::from("erin"), String::from("2")),
+            ],
+        };
+        let res = execute(deps.as_mut(), env, mock_info("carol", &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "batch_transfer"),
+                attr("from", "carol"),
+                attr("count", "2"),
+                attr("to", "dave"),
+                attr("token_id", "1"),
+                attr("to", "erin"),
+                attr("token_id", "2"),
+            ]
+        );
+        let token = query_tokens(deps.as_ref(), 2u64).unwrap();
+        assert_eq!(token.owner, Addr::unchecked("erin"));
+        assert!(approvals_of(deps.as_ref(), 2).is_empty());
     }
 
     #[test]
-    fn revoke() {
+    fn soulbound() {
+        use crate::msg::{NftInfoResponse, QueryMsg};
+        use crate::query::query;
+
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let info = mock_info("creator", &coins(0, &DENOM.to_string()));
 
+        let info = mock_info("minter", &[]);
         let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-
-        // Mint a new token
-        let mint_msg = mint_msg("owner".to_string());
-        let _res = handle_mint(deps.as_mut(), env.clone(), info.clone(), mint_msg).unwrap();
-
-        // Approve operator1
-        let approve_msg = ExecuteMsg::Approve {
-            operator: "operator1".to_string(),
-            token_id: 1u64,
-            expires: None,
-        };
-        let info = mock_info("owner", &coins(0, &DENOM.to_string()));
-        execute(deps.as_mut(), env.clone(), info.clone(), approve_msg).unwrap();
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        // Approve operator2
-        handle_approve(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            "operator2",
-            1u64,
-            None,
-        )
-        .unwrap();
+        for _ in 0..2 {
+            let mut msg = mint_msg("alice".to_string());
+            msg.transferable = Some(false);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::Mint(msg),
+            )
+            .unwrap();
+        }
 
+        // Soulbound tokens are never listed
         let token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        assert_eq!(token.approvals[0].operator, Addr::unchecked("operator1"));
-        assert_eq!(token.approvals[0].expires, Expiration::Never {});
+        assert!(!token.transferable);
+        assert!(!token.listed);
 
-        // Successful approval revoke
-        // * by owner
-        let revoke_msg = ExecuteMsg::Revoke {
-            operator: "operator1".to_string(),
-            token_id: 1u64,
+        let msg = QueryMsg::NftInfo {
+            token_id: String::from("1"),
         };
+        let res: NftInfoResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert!(!res.transferable);
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 4);
-
-        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        assert_eq!(token.approvals.len(), 1);
-
-        // * by operator
-        handle_approve_all(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            String::from("operator"),
-            None,
-        )
-        .unwrap();
+        // Give carol full control of alice's tokens
+        let msg = ExecuteMsg::ApproveAll {
+            operator: String::from("carol"),
+            expires: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
 
-        let info = mock_info("operator", &coins(0, &DENOM.to_string()));
-        handle_revoke(
+        // Unsuccessful transfers
+        // * by the owner
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("bob"),
+            token_id: String::from("1"),
+        };
+        let res = execute(
             deps.as_mut(),
             env.clone(),
-            info.clone(),
-            String::from("operator2"),
-            1u64,
+            mock_info("alice", &[]),
+            msg.clone(),
         )
-        .unwrap();
-
-        let token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        assert_eq!(token.approvals.len(), 0);
+        .unwrap_err();
+        match res {
+            ContractError::NonTransferable { token_id } => assert_eq!(token_id, 1),
+            e => panic!("{:?}", e),
+        };
 
-        // Unsuccessful approval revoke
-        // * Invalid token id
-        let revoke_msg = ExecuteMsg::Revoke {
-            operator: "operator".to_string(),
-            token_id: 2u64,
+        // * by an operator
+        let res = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), msg).unwrap_err();
+        match res {
+            ContractError::NonTransferable { .. } => {}
+            e => panic!("{:?}", e),
         };
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap_err();
+        // * sent to a contract
+        let msg = ExecuteMsg::SendNft {
+            contract: String::from("contract"),
+            token_id: String::from("1"),
+            msg: to_binary("hello").unwrap(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
         match res {
-            ContractError::Std(StdError::NotFound { .. }) => {}
+            ContractError::NonTransferable { .. } => {}
             e => panic!("{:?}", e),
         };
 
-        // * Approval being revoked for an address that isn't approved.
-        let revoke_msg = ExecuteMsg::Revoke {
-            operator: "unknown".to_string(),
-            token_id: 1u64,
+        // * approved for a single token
+        let msg = ExecuteMsg::Approve {
+            operator: String::from("bob"),
+            token_id: String::from("1"),
+            expires: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
+        match res {
+            ContractError::NonTransferable { .. } => {}
+            e => panic!("{:?}", e),
         };
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 4);
+        // * put up for sale
+        let msg = ExecuteMsg::SetPrice {
+            token_id: String::from("1"),
+            price: coins(100, DENOM),
+            price_mode: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
+        match res {
+            ContractError::NonTransferable { .. } => {}
+            e => panic!("{:?}", e),
+        };
 
-        // * Unauthorised sender
-        let info = mock_info("owner2", &coins(0, &DENOM.to_string()));
-        let revoke_msg = ExecuteMsg::Revoke {
-            operator: "operator".to_string(),
-            token_id: 1u64,
+        // * sold to an offer, stored directly as none can be made on the token
+        let offer = Offer {
+            token_id: String::from("1"),
+            bidder: Addr::unchecked("bob"),
+            amount: coins(100, DENOM),
+            expires: Expiration::Never {},
+        };
+        offers()
+            .save(&mut deps.storage, (1, &offer.bidder), &offer)
+            .unwrap();
+        let msg = ExecuteMsg::AcceptOffer {
+            token_id: String::from("1"),
+            bidder: String::from("bob"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap_err();
+        match res {
+            ContractError::NonTransferable { .. } => {}
+            e => panic!("{:?}", e),
         };
+        offers()
+            .remove(&mut deps.storage, (1, &offer.bidder))
+            .unwrap();
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), revoke_msg).unwrap_err();
+        // Unsuccessful burn
+        // * operators cannot burn soulbound tokens
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("1"),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), msg).unwrap_err();
         match res {
             ContractError::Unauthorized => {}
             e => panic!("{:?}", e),
         };
+
+        // Successful burns
+        // * revoked by the minter
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // * by the owner
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("2"),
+        };
+        execute(deps.as_mut(), env, mock_info("alice", &[]), msg).unwrap();
+        assert_eq!(query_config(deps.as_ref()).unwrap().token_count, 0);
     }
 
     #[test]
-    fn revoke_all() {
+    fn approval_housekeeping() {
         let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("owner", &coins(0u128, &DENOM.to_string()));
+        let mut env = mock_env();
 
-        // Approve an address for all tokens
-        let res = handle_approve_all(
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("alice".to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let owner_info = mock_info("alice", &[]);
+
+        // Approving the same operator twice updates its expiry
+        let soon = Expiration::AtHeight(env.block.height + 10);
+        handle_approve(
             deps.as_mut(),
             env.clone(),
-            info.clone(),
-            String::from("operator"),
+            owner_info.clone(),
+            "bob",
+            1u64,
+            Some(soon),
+        )
+        .unwrap();
+        handle_approve(
+            deps.as_mut(),
+            env.clone(),
+            owner_info.clone(),
+            "bob",
+            1u64,
             None,
         )
         .unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 3);
-
-        // Check that the new approval exists in correct format
-        let key = (&Addr::unchecked("owner"), &Addr::unchecked("operator"));
-        assert!(OPERATORS.has(&deps.storage, key));
         assert_eq!(
-            OPERATORS.load(&deps.storage, key).unwrap(),
-            Expiration::Never {}
+            approvals_of(deps.as_ref(), 1),
+            vec![Approval {
+                operator: Addr::unchecked("bob"),
+                expires: Expiration::Never {},
+            }]
         );
 
-        // Revoke previously provided approval
-        let res = handle_revoke_all(
+        // * per token cap
+        for i in 1..MAX_APPROVALS_PER_TOKEN {
+            let operator = format!("operator{}", i);
+            handle_approve(
+                deps.as_mut(),
+                env.clone(),
+                owner_info.clone(),
+                &operator,
+                1u64,
+                Some(soon),
+            )
+            .unwrap();
+        }
+        let res = handle_approve(
             deps.as_mut(),
             env.clone(),
-            info.clone(),
-            String::from("operator"),
+            owner_info.clone(),
+            "carol",
+            1u64,
+            None,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::TooManyApprovals { max } => assert_eq!(max, 20),
+            e => panic!("{:?}", e),
+        };
+
+        // Expired approvals are dropped when the token is written
+        env.block.height += 10;
+        handle_approve(
+            deps.as_mut(),
+            env.clone(),
+            owner_info.clone(),
+            "carol",
+            1u64,
+            None,
         )
         .unwrap();
-        assert_eq!(res.messages.len(), 0);
-        assert_eq!(res.attributes.len(), 3);
-        assert!(!OPERATORS.has(&deps.storage, key));
+        assert_eq!(approvals_of(deps.as_ref(), 1).len(), 2);
 
-        // Unsuccessful revoke
-        let res = handle_revoke_all(
+        // Anyone can prune the expired approvals
+        env.block.height -= 10;
+        handle_approve(
             deps.as_mut(),
             env.clone(),
-            info.clone(),
-            String::from("operator"),
+            owner_info,
+            "dave",
+            2u64,
+            Some(soon),
         )
-        .unwrap_err();
-        match res {
-            ContractError::ApprovalNotFound { .. } => {}
-            e => panic!("{:?}", e),
+        .unwrap();
+        env.block.height += 10;
+
+        let msg = ExecuteMsg::PruneExpired {
+            token_ids: vec![String::from("1"), String::from("2")],
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.attributes[2], attr("pruned", "1"));
+        assert!(approvals_of(deps.as_ref(), 2).is_empty());
+
+        // * unknown token
+        let msg = ExecuteMsg::PruneExpired {
+            token_ids: vec![String::from("3")],
         };
+        execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap_err();
     }
 
     #[test]
-    fn transfer_nft_by_owner() {
+    fn pause_keeps_exits_open() {
         let mut deps = mock_dependencies();
         let env = mock_env();
 
-        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let info = mock_info("admin", &[]);
         let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-        let mint_msg = mint_msg("creator".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("alice".to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let alice = mock_info("alice", &[]);
+
+        // Approvals, an auction and offers made before the pause
+        let msgs = vec![
+            (
+                alice.clone(),
+                ExecuteMsg::Approve {
+                    operator: String::from("bob"),
+                    token_id: String::from("1"),
+                    expires: None,
+                },
+            ),
+            (
+                alice.clone(),
+                ExecuteMsg::ApproveAll {
+                    operator: String::from("carol"),
+                    expires: None,
+                },
+            ),
+            (
+                alice.clone(),
+                ExecuteMsg::StartAuction {
+                    token_id: String::from("2"),
+                    reserve_price: Coin::new(100, DENOM),
+                    min_increment: Uint128::zero(),
+                    ends_at: Expiration::AtHeight(env.block.height + 10),
+                },
+            ),
+            (
+                mock_info("bob", &coins(100, DENOM)),
+                ExecuteMsg::MakeOffer {
+                    token_id: String::from("1"),
+                    expires: None,
+                },
+            ),
+            (
+                mock_info("carol", &coins(100, DENOM)),
+                ExecuteMsg::MakeCollectionOffer {
+                    price: Coin::new(100, DENOM),
+                    quantity: 1,
+                    expires: None,
+                },
+            ),
+        ];
+        for (info, msg) in msgs {
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
 
-        let msg = ExecuteMsg::Mint(mint_msg);
-        let res: Response = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(4, res.attributes.len());
+        let msg = ExecuteMsg::Pause { targets: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Backing out still works while everything is paused
+        let msgs = vec![
+            (
+                alice.clone(),
+                ExecuteMsg::Revoke {
+                    operator: String::from("bob"),
+                    token_id: String::from("1"),
+                },
+            ),
+            (
+                alice.clone(),
+                ExecuteMsg::RevokeAll {
+                    operator: String::from("carol"),
+                },
+            ),
+            (
+                alice.clone(),
+                ExecuteMsg::PruneExpired {
+                    token_ids: vec![String::from("1")],
+                },
+            ),
+            (
+                alice.clone(),
+                ExecuteMsg::Delist {
+                    token_id: String::from("1"),
+                },
+            ),
+            (
+                alice,
+                ExecuteMsg::CancelAuction {
+                    token_id: String::from("2"),
+                },
+            ),
+            (
+                mock_info("bob", &[]),
+                ExecuteMsg::CancelOffer {
+                    token_id: String::from("1"),
+                },
+            ),
+            (
+                mock_info("carol", &[]),
+                ExecuteMsg::CancelCollectionOffer { offer_id: 1 },
+            ),
+            (
+                info,
+                ExecuteMsg::SetRoyalty {
+                    token_id: None,
+                    royalty: None,
+                },
+            ),
+        ];
+        for (info, msg) in msgs {
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
 
-        let info = mock_info("creator", &coins(0u128, &DENOM.to_string()));
-        let msg = ExecuteMsg::TransferNft {
-            recipient: String::from("recipient"),
-            token_id: 1,
-        };
-        let res: Response = execute(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(4, res.attributes.len());
+        assert!(approvals_of(deps.as_ref(), 1).is_empty());
+        assert!(!query_tokens(deps.as_ref(), 1u64).unwrap().listed);
+        assert!(!AUCTIONS.has(&deps.storage, 2));
     }
 
     #[test]
-    fn transfer_nft_by_operator() {
-        // Setup the necessary environment
+    fn pause() {
+        use crate::msg::QueryMsg;
+        use crate::query::query;
+
         let mut deps = mock_dependencies();
         let env = mock_env();
 
-        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let info = mock_info("admin", &[]);
         let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg("alice".to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let alice = mock_info("alice", &[]);
 
-        let mint_msg = mint_msg("creator".to_string());
+        // Unsuccessful pause
+        // * sender is not the admin
+        let msg = ExecuteMsg::Pause { targets: None };
+        let res = execute(deps.as_mut(), env.clone(), alice.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Unauthorized => {}
+            e => panic!("{:?}", e),
+        };
 
-        let msg = ExecuteMsg::Mint(mint_msg);
-        let res: Response = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(4, res.attributes.len());
+        // Pause minting only
+        let msg = ExecuteMsg::Pause {
+            targets: Some(vec![PauseTarget::Minting]),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(res.events[0].ty, "pause");
+        assert_eq!(res.events[0].attributes, vec![attr("targets", "minting")]);
 
-        // Add approval to the token
-        let mut token = query_tokens(deps.as_ref(), 1u64).unwrap();
-        token.approvals.push(Approval {
-            operator: Addr::unchecked("operator"),
-            expires: Expiration::Never {},
-        });
-        TOKENS.save(&mut deps.storage, 1u64, &token).unwrap();
+        let msg = ExecuteMsg::Mint(mint_msg("alice".to_string()));
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        match res {
+            ContractError::Paused { target } => assert_eq!(target, PauseTarget::Minting),
+            e => panic!("{:?}", e),
+        };
+        assert_eq!(res.to_string(), "minting is paused");
 
-        // *operator* should now be capable of transferring the token
-        let info = mock_info("operator", &coins(0u128, &DENOM.to_string()));
+        // * transfers still work
         let msg = ExecuteMsg::TransferNft {
-            recipient: String::from("recipient"),
-            token_id: 1,
+            recipient: String::from("bob"),
+            token_id: String::from("2"),
         };
-        let res: Response = execute(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(4, res.attributes.len());
+        execute(deps.as_mut(), env.clone(), alice.clone(), msg).unwrap();
+
+        // Pause everything
+        let msg = ExecuteMsg::Pause { targets: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.paused, PauseTarget::ALL.to_vec());
+
+        let msgs = vec![
+            (
+                ExecuteMsg::TransferNft {
+                    recipient: String::from("bob"),
+                    token_id: String::from("1"),
+                },
+                PauseTarget::Transfers,
+            ),
+            (
+                ExecuteMsg::Burn {
+                    token_id: String::from("1"),
+                },
+                PauseTarget::Transfers,
+            ),
+            (
+                ExecuteMsg::Approve {
+                    operator: String::from("bob"),
+                    token_id: String::from("1"),
+                    expires: None,
+                },
+                PauseTarget::Approvals,
+            ),
+            (
+                ExecuteMsg::ApproveAll {
+                    operator: String::from("bob"),
+                    expires: None,
+                },
+                PauseTarget::Approvals,
+            ),
+            (
+                ExecuteMsg::SetPrice {
+                    token_id: String::from("1"),
+                    price: coins(10, DENOM),
+                    price_mode: None,
+                },
+                PauseTarget::Marketplace,
+            ),
+        ];
+        for (msg, paused) in msgs {
+            let res = execute(deps.as_mut(), env.clone(), alice.clone(), msg).unwrap_err();
+            match res {
+                ContractError::Paused { target } => assert_eq!(target, paused),
+                e => panic!("{:?}", e),
+            };
+        }
+
+        let msg = ExecuteMsg::Buy {
+            token_id: String::from("1"),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &coins(1000, DENOM)),
+            msg,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Paused { target } => assert_eq!(target, PauseTarget::Marketplace),
+            e => panic!("{:?}", e),
+        };
+
+        // * delisting still works, and so do queries
+        let msg = ExecuteMsg::Delist {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), alice.clone(), msg).unwrap();
+        let msg = QueryMsg::OwnerOf {
+            token_id: String::from("1"),
+            include_expired: None,
+        };
+        query(deps.as_ref(), env.clone(), msg).unwrap();
+
+        // Unpause transfers
+        let msg = ExecuteMsg::Unpause {
+            targets: Some(vec![PauseTarget::Transfers]),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(res.events[0].ty, "unpause");
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("bob"),
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), alice, msg).unwrap();
+
+        // Unpause everything
+        let msg = ExecuteMsg::Unpause { targets: None };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert!(query_config(deps.as_ref()).unwrap().paused.is_empty());
+        let msg = ExecuteMsg::Mint(mint_msg("alice".to_string()));
+        execute(deps.as_mut(), env, info, msg).unwrap();
     }
 }
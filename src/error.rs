@@ -1,11 +1,18 @@
-use cosmwasm_std::{Coin, StdError};
+use cosmwasm_std::{Coin, Decimal, StdError};
+use cw20::Cw20CoinVerified;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
+use crate::msg::PauseTarget;
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     /// Only authorized entities are allowed to execute.
     #[error("Unauthorized")]
     Unauthorized,
@@ -26,10 +33,124 @@ pub enum ContractError {
     #[error("token_id: {token_id} does not exist")]
     InvalidToken { token_id: u64 },
 
+    /// Custom token id is already in use.
+    #[error("token_id: {token_id} has already been claimed")]
+    Claimed { token_id: String },
+
+    /// Token is not up for sale.
+    #[error("token_id: {token_id} is not listed for sale")]
+    NotListed { token_id: u64 },
+
+    /// Token has as many approvals as allowed.
+    #[error("Token cannot have more than {max} approvals")]
+    TooManyApprovals { max: u32 },
+
+    /// Action has been paused by the admin.
+    #[error("{target} is paused")]
+    Paused { target: PauseTarget },
+
+    /// Token is soulbound.
+    #[error("token_id: {token_id} is not transferable")]
+    NonTransferable { token_id: u64 },
+
+    /// Token is locked by a running auction.
+    #[error("token_id: {token_id} is being auctioned")]
+    InAuction { token_id: u64 },
+
+    /// Auction no longer accepts bids.
+    #[error("Auction for token_id: {token_id} has ended")]
+    AuctionEnded { token_id: u64 },
+
+    /// Auction cannot be settled before it ends.
+    #[error("Auction for token_id: {token_id} has not ended yet")]
+    AuctionNotEnded { token_id: u64 },
+
+    /// Bid is below the reserve price or the minimum increment.
+    #[error("Bid too low. Expected at least {min:?}")]
+    BidTooLow { min: Coin },
+
+    /// Auction has received as many bids as allowed.
+    #[error("Auction cannot have more than {max} bids")]
+    TooManyBids { max: u64 },
+
+    /// Bidder has no offer on the token.
+    #[error("No offer from {bidder} on token_id: {token_id}")]
+    OfferNotFound { token_id: u64, bidder: String },
+
+    /// Offer can no longer be accepted.
+    #[error("Offer from {bidder} on token_id: {token_id} has expired")]
+    OfferExpired { token_id: u64, bidder: String },
+
+    /// Collection offer can no longer be accepted.
+    #[error("Collection offer {offer_id} has expired")]
+    CollectionOfferExpired { offer_id: u64 },
+
+    /// Royalty share is above the allowed maximum.
+    #[error("Royalty share cannot be more than {max}")]
+    RoyaltyTooHigh { max: Decimal },
+
+    /// No more tokens can be minted.
+    #[error("Max supply of {max_supply} tokens has been reached")]
+    MaxSupplyReached { max_supply: u64 },
+
+    /// Public mint is closed.
+    #[error("Public mint is not open")]
+    PublicMintClosed,
+
+    /// Allowlist mint is closed.
+    #[error("Allowlist mint is not open")]
+    AllowlistMintClosed,
+
+    /// Merkle proof does not match the allowlist root.
+    #[error("Invalid allowlist proof")]
+    InvalidProof,
+
+    /// Address has minted as many tokens as allowed.
+    #[error("Cannot mint more than {limit} tokens per address")]
+    MintLimitReached { limit: u32 },
+
+    /// Batch message has more items than allowed.
+    #[error("Batch cannot have more than {max_batch_size} items")]
+    BatchTooLarge { max_batch_size: u32 },
+
+    /// Platform fee is above the allowed maximum.
+    #[error("Platform fee cannot be more than {max_bps} basis points")]
+    FeeTooHigh { max_bps: u16 },
+
     /// Amount sent in not equal to the price of the NFT.
     #[error("Invalid amount. Expected {val:?} received {funds:?}")]
     InvalidAmount { val: Coin, funds: Coin },
 
+    /// Payment does not include a denom which is part of the price.
+    #[error("Payment is missing denom: {denom}")]
+    MissingDenom { denom: String },
+
+    /// Payment includes a denom which is not part of the price.
+    #[error("Denom: {denom} is not part of the price")]
+    UnexpectedDenom { denom: String },
+
+    /// CW20 tokens sent are not the CW20 price of the NFT or of the mint.
+    #[error("Invalid CW20 payment. Expected {val} received {funds}")]
+    InvalidCw20Amount {
+        val: Cw20CoinVerified,
+        funds: Cw20CoinVerified,
+    },
+
+    /// Stored contract is not an instance of this contract.
+    #[error("Cannot migrate from a different contract: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    /// Stored contract version is newer than this one.
+    #[error("Cannot migrate from version {previous_version} to {new_version}")]
+    CannotMigrateVersion {
+        previous_version: String,
+        new_version: String,
+    },
+
+    /// Contract version could not be parsed.
+    #[error("Semver parsing error: {0}")]
+    SemVer(#[from] semver::Error),
+
     /// Any other error not which has not been covered.
     #[error("Following error occured: {val:?}")]
     CustomError { val: String },
@@ -8,6 +8,7 @@
 
 pub mod contract;
 mod error;
+mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
@@ -0,0 +1,210 @@
+//! Upgrades the stored state from older versions of this contract.
+//! Each release which changes the layout of `State` or `TokenInfo` gets
+//! its own submodule, describing the layout it migrates from.
+
+pub mod v0_1 {
+    //! Layout used by v0.1.x, before tokens could be listed and burned.
+
+    use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage};
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    use crate::contract::DEFAULT_MAX_BATCH_SIZE;
+    use crate::msg::{Approval, PriceMode};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct State {
+        pub name: String,
+        pub symbol: String,
+        pub minter: Addr,
+        pub num_tokens: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct TokenInfo {
+        pub owner: Addr,
+        pub approvals: Vec<Approval>,
+        pub base_price: Vec<Coin>,
+        pub token_uri: Option<String>,
+        pub token_id: u64,
+    }
+
+    pub const CONFIG_V0_1: Item<State> = Item::new("config");
+    pub const TOKENS_V0_1: Map<u64, TokenInfo> = Map::new("tokens");
+
+    /// Split `num_tokens` into `next_token_id` and `token_count` and mark
+    /// every token as listed, as they were all implicitly for sale. The
+    /// config is left in the v0.3 layout and tokens in the v0.2 layout, for
+    /// the later migrations to finish the job.
+    pub fn migrate(storage: &mut dyn Storage) -> StdResult<()> {
+        let old_tokens: StdResult<Vec<_>> = TOKENS_V0_1
+            .range(storage, None, None, Order::Ascending)
+            .collect();
+        let old_tokens = old_tokens?;
+
+        let old_config = CONFIG_V0_1.load(storage)?;
+        let config = super::v0_3::State {
+            name: old_config.name,
+            symbol: old_config.symbol,
+            minter: Some(old_config.minter.clone()),
+            pending_minter: None,
+            admin: old_config.minter,
+            next_token_id: old_config.num_tokens + 1,
+            token_count: old_tokens.len() as u64,
+            max_supply: None,
+            public_mint: None,
+            allowlist: None,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            default_royalty: None,
+            next_collection_offer_id: 1,
+            platform_fee: None,
+        };
+        super::v0_3::CONFIG_V0_3.save(storage, &config)?;
+
+        for (token_id, old) in old_tokens {
+            let token = super::v0_2::TokenInfo {
+                owner: old.owner,
+                approvals: old.approvals,
+                base_price: old.base_price,
+                price_mode: PriceMode::AllOf,
+                cw20_price: None,
+                listed: true,
+                transferable: true,
+                token_uri: old.token_uri,
+                extension: None,
+                royalty: None,
+                token_id: old.token_id,
+                custom_id: None,
+            };
+            super::v0_2::TOKENS_V0_2.save(storage, token_id, &token)?;
+        }
+        Ok(())
+    }
+}
+
+pub mod v0_2 {
+    //! Layout used by v0.2.x, with the approvals embedded in each token.
+
+    use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage};
+    use cw20::Cw20CoinVerified;
+    use cw_storage_plus::Map;
+    use serde::{Deserialize, Serialize};
+
+    use crate::msg::{Approval, Metadata, PriceMode, Royalty};
+    use crate::state::{self, token_approvals, tokens};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct TokenInfo {
+        pub owner: Addr,
+        pub approvals: Vec<Approval>,
+        pub base_price: Vec<Coin>,
+        pub price_mode: PriceMode,
+        pub cw20_price: Option<Cw20CoinVerified>,
+        pub listed: bool,
+        pub transferable: bool,
+        pub token_uri: Option<String>,
+        pub extension: Option<Metadata>,
+        pub royalty: Option<Royalty>,
+        pub token_id: u64,
+        pub custom_id: Option<String>,
+    }
+
+    pub const TOKENS_V0_2: Map<u64, TokenInfo> = Map::new("tokens");
+
+    /// Move the approvals of every token into `token_approvals`, keeping
+    /// the last approval of an operator if it was approved more than once,
+    /// and (re)build the owner index of the tokens.
+    pub fn migrate(storage: &mut dyn Storage) -> StdResult<()> {
+        let old_tokens: StdResult<Vec<_>> = TOKENS_V0_2
+            .range(storage, None, None, Order::Ascending)
+            .collect();
+
+        for (token_id, old) in old_tokens? {
+            for appr in old.approvals {
+                token_approvals().save(storage, (token_id, &appr.operator), &appr)?;
+            }
+
+            let token = state::TokenInfo {
+                owner: old.owner,
+                base_price: old.base_price,
+                price_mode: old.price_mode,
+                cw20_price: old.cw20_price,
+                listed: old.listed,
+                transferable: old.transferable,
+                token_uri: old.token_uri,
+                extension: old.extension,
+                royalty: old.royalty,
+                token_id: old.token_id,
+                custom_id: old.custom_id,
+            };
+            // Index entries only depend on the owner, which has not changed
+            tokens().replace(storage, token_id, Some(&token), None)?;
+        }
+        Ok(())
+    }
+}
+
+pub mod v0_3 {
+    //! Layout used by v0.3.x, before the contract could be paused and
+    //! before the operator side of `OPERATORS` was kept.
+
+    use cosmwasm_std::{Addr, Order, StdResult, Storage};
+    use cw_storage_plus::Item;
+    use serde::{Deserialize, Serialize};
+
+    use crate::msg::{AllowlistConfig, PlatformFee, PublicMintConfig, Royalty};
+    use crate::state::{self, CONFIG, OPERATORS, OPERATOR_OWNERS};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct State {
+        pub name: String,
+        pub symbol: String,
+        pub minter: Option<Addr>,
+        pub pending_minter: Option<Addr>,
+        pub admin: Addr,
+        pub next_token_id: u64,
+        pub token_count: u64,
+        pub max_supply: Option<u64>,
+        pub public_mint: Option<PublicMintConfig>,
+        pub allowlist: Option<AllowlistConfig>,
+        pub max_batch_size: u32,
+        pub default_royalty: Option<Royalty>,
+        pub next_collection_offer_id: u64,
+        pub platform_fee: Option<PlatformFee>,
+    }
+
+    pub const CONFIG_V0_3: Item<State> = Item::new("config");
+
+    /// Add the (empty) list of paused targets to the config and build
+    /// `OPERATOR_OWNERS` from the existing `OPERATORS`. Nothing is paused
+    /// after the migration.
+    pub fn migrate(storage: &mut dyn Storage) -> StdResult<()> {
+        let old_config = CONFIG_V0_3.load(storage)?;
+        let config = state::State {
+            name: old_config.name,
+            symbol: old_config.symbol,
+            minter: old_config.minter,
+            pending_minter: old_config.pending_minter,
+            admin: old_config.admin,
+            next_token_id: old_config.next_token_id,
+            token_count: old_config.token_count,
+            max_supply: old_config.max_supply,
+            public_mint: old_config.public_mint,
+            allowlist: old_config.allowlist,
+            max_batch_size: old_config.max_batch_size,
+            default_royalty: old_config.default_royalty,
+            next_collection_offer_id: old_config.next_collection_offer_id,
+            platform_fee: old_config.platform_fee,
+            paused: vec![],
+        };
+        CONFIG.save(storage, &config)?;
+
+        let operators: StdResult<Vec<_>> = OPERATORS
+            .range(storage, None, None, Order::Ascending)
+            .collect();
+        for ((owner, operator), expires) in operators? {
+            OPERATOR_OWNERS.save(storage, (&operator, &owner), &expires)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Defines *InstantiateMsg*, *ExecuteMsg* and *QueryMsg*.
 
-use cosmwasm_std::{Addr, Binary, Coin};
+use std::fmt;
+
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128};
+use cw20::{Cw20Coin, Cw20CoinVerified, Cw20ReceiveMsg};
 use cw721::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -12,31 +15,43 @@ pub struct InstantiateMsg {
     pub name: String,
     /// Symbol of the NFT
     pub symbol: String,
-    // /// Minter has the permission to mint new tokens
-    // pub minter: String,
+    /// Minter has the permission to mint new tokens.
+    /// Defaults to the sender of this message.
+    pub minter: Option<String>,
+    /// Admin has the permission to configure the platform fee and the
+    /// public mint. Defaults to the sender of this message.
+    pub admin: Option<String>,
+    /// Maximum number of tokens which can ever be minted, through any
+    /// path. Cannot be changed later. No limit if not given.
+    pub max_supply: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Transfer is a base message to move a token to another account without triggering actions
-    TransferNft { recipient: String, token_id: u64 },
+    TransferNft { recipient: String, token_id: String },
     /// Send is a base message to transfer a token to a contract and trigger an action
     /// on the receiving contract.
     SendNft {
         contract: String,
-        token_id: u64,
+        token_id: String,
         msg: Binary,
     },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
         operator: String,
-        token_id: u64,
+        token_id: String,
         expires: Option<Expiration>,
     },
     /// Remove previously granted Approval
-    Revoke { operator: String, token_id: u64 },
+    Revoke { operator: String, token_id: String },
     /// Allows operator to transfer / send any token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     ApproveAll {
@@ -45,8 +60,165 @@ pub enum ExecuteMsg {
     },
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
+    /// Drop the expired approvals of the given tokens. Can be called by anyone.
+    PruneExpired { token_ids: Vec<String> },
     /// Mint a new token with the details as in MintMsg.
     Mint(MintMsg),
+    /// Mint several tokens at once. Either all of them are minted or none,
+    /// as a failing item reverts the whole transaction.
+    BatchMint(Vec<MintMsg>),
+    /// Transfer several tokens at once, as (recipient, token_id) pairs.
+    /// Either all of them are transferred or none.
+    BatchTransfer { transfers: Vec<(String, String)> },
+    /// Approve operator for several tokens at once. Either all of them are
+    /// approved or none.
+    BatchApprove {
+        operator: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    },
+    /// Set the maximum number of items in a single batch message. Can only
+    /// be called by the admin.
+    UpdateMaxBatchSize { max_batch_size: u32 },
+    /// Propose a new minter. The proposed address has to accept the role
+    /// using AcceptMinter before it takes effect.
+    ProposeMinter { new_minter: String },
+    /// Accept the minter role previously proposed to the sender.
+    AcceptMinter {},
+    /// Give up the minter role, permanently closing minting.
+    RenounceMinter {},
+    /// Put the token up for an English auction. The token cannot be moved
+    /// until the auction is settled or cancelled.
+    StartAuction {
+        token_id: String,
+        reserve_price: Coin,
+        min_increment: Uint128,
+        ends_at: Expiration,
+    },
+    /// Bid on the token with the funds sent along. Outbid bids are refunded.
+    /// An auction accepts a bounded number of bids.
+    PlaceBid { token_id: String },
+    /// Cancel the auction, only possible as long as nobody has bid.
+    CancelAuction { token_id: String },
+    /// Hand the token to the highest bidder and pay the seller, once the
+    /// auction has ended. Can be called by anyone.
+    SettleAuction { token_id: String },
+    /// List the token in a Dutch auction, where the price decays from
+    /// start_price at start_time down to floor_price at end_time. The token
+    /// is bought through Buy at the current price. Delist ends the auction.
+    StartDutchAuction {
+        token_id: String,
+        start_price: Coin,
+        floor_price: Uint128,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        curve: DecayCurve,
+    },
+    /// Offer to buy the token with the funds sent along, which are held by
+    /// the contract until the offer is accepted or cancelled. A new offer
+    /// on the same token replaces the previous one of the sender.
+    MakeOffer {
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Withdraw the offer made on the token and get the funds back,
+    /// including after it has expired.
+    CancelOffer { token_id: String },
+    /// Sell the token to the bidder for the funds held in their offer.
+    /// Can be called by the owner or an approved operator.
+    AcceptOffer { token_id: String, bidder: String },
+    /// Offer to buy up to `quantity` tokens of the collection at `price`
+    /// each. The funds sent along have to cover every token and are held
+    /// by the contract until the offer is filled or cancelled.
+    MakeCollectionOffer {
+        price: Coin,
+        quantity: u32,
+        expires: Option<Expiration>,
+    },
+    /// Withdraw a collection offer and get back the funds of the tokens
+    /// which have not been bought, including after it has expired.
+    CancelCollectionOffer { offer_id: u64 },
+    /// Sell the token to the collection offer. Can be called by the owner
+    /// or an approved operator.
+    AcceptCollectionOffer { token_id: String, offer_id: u64 },
+    /// Mint a token to the sender by paying the public mint price. Only
+    /// possible while the public mint phase is open and the minter has not
+    /// been renounced.
+    PublicMint {},
+    /// Mint a token to the sender during the allowlist phase. The proof
+    /// shows that (sender, allocation) is part of the allowlist Merkle
+    /// root, and the sender can mint at most `allocation` tokens. Closed
+    /// once the minter has been renounced.
+    MintAllowlisted { proof: Vec<String>, allocation: u32 },
+    /// Configure the allowlist mint phase. Passing no configuration closes
+    /// it. Can only be called by the admin, and cannot open the phase once
+    /// the minter has been renounced.
+    UpdateAllowlist { allowlist: Option<AllowlistConfig> },
+    /// Replace the Merkle root of the allowlist, keeping the rest of its
+    /// configuration. Can only be called by the admin.
+    UpdateMerkleRoot { merkle_root: String },
+    /// Configure the public mint phase. Passing no configuration closes it.
+    /// Can only be called by the admin, and cannot open the phase once the
+    /// minter has been renounced.
+    UpdatePublicMint {
+        public_mint: Option<PublicMintConfig>,
+    },
+    /// Send the proceeds of the public mint to the recipient, or to the
+    /// admin if none is given. Can only be called by the admin.
+    WithdrawMintProceeds { recipient: Option<String> },
+    /// Pause the given groups of actions, or all of them if none are given.
+    /// Can only be called by the admin. Users can still back out while
+    /// paused: revoking and pruning approvals, delisting, and cancelling
+    /// auctions and offers are never paused, and neither are the royalty
+    /// and configuration updates of the minter and admin.
+    Pause { targets: Option<Vec<PauseTarget>> },
+    /// Resume the given groups of actions, or all of them if none are
+    /// given. Can only be called by the admin.
+    Unpause { targets: Option<Vec<PauseTarget>> },
+    /// Set the fee taken by the platform on every sale. Passing no fee
+    /// removes it. Can only be called by the admin.
+    UpdatePlatformFee { platform_fee: Option<PlatformFee> },
+    /// Set the royalty of the token, or the collection default if no token
+    /// is given. Passing no royalty removes it. Can only be called by the minter.
+    SetRoyalty {
+        token_id: Option<String>,
+        royalty: Option<Royalty>,
+    },
+    /// Burn the token permanently. Can be called by the owner or an approved operator.
+    Burn { token_id: String },
+    /// Buy the token by paying its asking price. The funds sent along
+    /// must match every coin in the token's `base_price`, or exactly one
+    /// of them if its price mode is `AnyOf`.
+    Buy { token_id: String },
+    /// Update the price of the token and list it for sale. The price mode
+    /// of the token is kept if none is given.
+    SetPrice {
+        token_id: String,
+        price: Vec<Coin>,
+        price_mode: Option<PriceMode>,
+    },
+    /// Price the token in a CW20 token and list it for sale. It can then
+    /// only be bought by sending the CW20 tokens with a Buy hook.
+    SetCw20Price { token_id: String, price: Cw20Coin },
+    /// Take the token off sale.
+    Delist { token_id: String },
+    /// Handle CW20 tokens sent to this contract, carrying a ReceiveMsg.
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Messages which can be sent along with CW20 tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Buy the token priced in the CW20 token sent. The amount sent must
+    /// match the token's `cw20_price`.
+    Buy { token_id: String },
+    /// Same as `ExecuteMsg::PublicMint`, paying the `cw20_price` of the
+    /// public mint with the CW20 token sent.
+    PublicMint {},
+    /// Same as `ExecuteMsg::MintAllowlisted`, paying the `cw20_price` of
+    /// the allowlist mint with the CW20 token sent.
+    MintAllowlisted { proof: Vec<String>, allocation: u32 },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -57,18 +229,284 @@ pub struct Approval {
     pub expires: Expiration,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    /// Token being auctioned
+    pub token_id: String,
+    /// Owner of the token when the auction started
+    pub seller: Addr,
+    /// Lowest acceptable first bid. Bids have to be made in the same denom.
+    pub reserve_price: Coin,
+    /// Amount by which every bid has to exceed the previous highest bid
+    pub min_increment: Uint128,
+    /// When bidding closes
+    pub ends_at: Expiration,
+    /// Highest bid so far, held by the contract
+    pub highest_bid: Option<Bid>,
+    /// Number of bids placed so far
+    pub bid_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Coin,
+    pub placed_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    /// Token the offer is made on
+    pub token_id: String,
+    /// Account which made the offer
+    pub bidder: Addr,
+    /// Funds held by the contract, paid to the owner when accepted
+    pub amount: Vec<Coin>,
+    /// When the offer can no longer be accepted
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionOffer {
+    /// Unique id of the offer
+    pub offer_id: u64,
+    /// Account which made the offer
+    pub bidder: Addr,
+    /// Price paid for each token
+    pub price: Coin,
+    /// Number of tokens which can still be bought
+    pub quantity: u32,
+    /// When the offer can no longer be accepted
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DutchAuction {
+    /// Token being auctioned
+    pub token_id: String,
+    /// Price when the auction starts
+    pub start_price: Coin,
+    /// Price once the auction has ended, in the same denom as start_price
+    pub floor_price: Uint128,
+    /// When the token can first be bought
+    pub start_time: Timestamp,
+    /// When the price reaches floor_price
+    pub end_time: Timestamp,
+    /// How the price decays between start_time and end_time
+    pub curve: DecayCurve,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DecayCurve {
+    /// Price decreases continuously
+    Linear,
+    /// Price decreases once every `interval` seconds
+    Stepwise { interval: u64 },
+}
+
+impl DutchAuction {
+    /// Price of the token at the given time.
+    pub fn price_at(&self, time: Timestamp) -> Coin {
+        let start = self.start_price.amount;
+        let duration = self.end_time.seconds() - self.start_time.seconds();
+        let mut elapsed = time
+            .seconds()
+            .saturating_sub(self.start_time.seconds())
+            .min(duration);
+        // The last step may be shorter, the floor is always reached at end_time
+        if let DecayCurve::Stepwise { interval } = self.curve {
+            if elapsed < duration {
+                elapsed -= elapsed % interval;
+            }
+        }
+
+        let decay = (start - self.floor_price).multiply_ratio(elapsed, duration);
+        Coin::new((start - decay).u128(), self.start_price.denom.clone())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MintMsg {
-    // /// Unique ID of the NFT
-    // pub token_id: u64,
+    /// Custom unique ID of the NFT. If not given, the next numeric id is used.
+    /// Custom ids cannot be numeric, as those are reserved for the numeric ids.
+    /// A token minted with a custom id is only known by it.
+    pub token_id: Option<String>,
     /// The owner of the newly minter NFT
     pub owner: String,
     /// Universal resource identifier for this NFT
     /// Should point to a JSON file that conforms to the ERC721
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
-    /// Price of the token
+    /// Price of the token. Can be left empty if `cw20_price` is given.
+    /// New tokens are listed at this price, unless they are soulbound.
     pub price: Vec<Coin>,
+    /// Price in a CW20 token, taking the place of `price`
+    pub cw20_price: Option<Cw20Coin>,
+    /// How `price` has to be paid. Defaults to `PriceMode::AllOf`.
+    pub price_mode: Option<PriceMode>,
+    /// Metadata stored on-chain along with the token
+    pub extension: Option<Metadata>,
+    /// Royalty paid on every sale of this token, in place of the collection default
+    pub royalty: Option<Royalty>,
+    /// Whether the token can ever leave its owner. Defaults to true.
+    pub transferable: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PublicMintConfig {
+    /// Price paid for every token minted. Minted tokens are listed at the
+    /// price they have been minted for, like any other new token.
+    pub price: Vec<Coin>,
+    /// Price in a CW20 token. When set, tokens can only be minted by
+    /// sending this amount of the CW20 token, and `price` may be empty.
+    pub cw20_price: Option<Cw20Coin>,
+    /// Number of tokens a single address can mint
+    pub per_address_limit: u32,
+    /// When the public mint opens
+    pub start_time: Timestamp,
+    /// When the public mint closes
+    pub end_time: Timestamp,
+    /// Token URI of the minted tokens is this prefix followed by their id
+    pub base_token_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistConfig {
+    /// Hex encoded sha256 Merkle root of the allowlist. Each leaf is the
+    /// sha256 hash of the UTF-8 string `"{address}:{allocation}"`, with the
+    /// allocation in decimal, e.g. `sha256("wasm1...:3")`. Each parent is
+    /// the sha256 hash of its two children concatenated, smallest first.
+    pub merkle_root: String,
+    /// Price paid for every token minted. Minted tokens are listed at the
+    /// price they have been minted for, like any other new token.
+    pub price: Vec<Coin>,
+    /// Price in a CW20 token. When set, tokens can only be minted by
+    /// sending this amount of the CW20 token, and `price` may be empty.
+    pub cw20_price: Option<Cw20Coin>,
+    /// Number of tokens a single address can mint, whatever its allocation
+    pub per_address_limit: u32,
+    /// When the allowlist mint opens
+    pub start_time: Timestamp,
+    /// When the allowlist mint closes
+    pub end_time: Timestamp,
+    /// Token URI of the minted tokens is this prefix followed by their id
+    pub base_token_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlatformFee {
+    /// Share of every sale taken as fee, in basis points
+    pub bps: u16,
+    /// Accounts the fee is split between
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipient {
+    /// Address receiving its part of the fee
+    pub address: String,
+    /// Weight of the recipient relative to the other recipients
+    pub weight: u32,
+}
+
+impl PlatformFee {
+    /// Fee owed to each recipient on a sale of `amount`, in the order of
+    /// `recipients`. Rounding leftovers go to the first recipient.
+    pub fn split(&self, amount: Uint128) -> Vec<Uint128> {
+        let fee = amount.multiply_ratio(self.bps, 10_000u128);
+        let total_weight: u128 = self.recipients.iter().map(|r| u128::from(r.weight)).sum();
+
+        let mut shares: Vec<Uint128> = self
+            .recipients
+            .iter()
+            .map(|r| fee.multiply_ratio(r.weight, total_weight))
+            .collect();
+        let paid: Uint128 = shares.iter().sum();
+        if let Some(first) = shares.first_mut() {
+            *first += fee - paid;
+        }
+        shares
+    }
+}
+
+/// How a price made of several coins has to be paid.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceMode {
+    /// Every coin of the price has to be paid
+    #[default]
+    AllOf,
+    /// Any single coin of the price has to be paid
+    AnyOf,
+}
+
+/// Group of actions which can be paused by the admin.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseTarget {
+    /// Every kind of mint
+    Minting,
+    /// Transfers, sends and burns
+    Transfers,
+    /// Granting approvals. Revoking them is always possible.
+    Approvals,
+    /// Listing, buying, auctions and offers. Delisting and cancelling
+    /// auctions without bids or offers is always possible.
+    Marketplace,
+}
+
+impl PauseTarget {
+    pub const ALL: [PauseTarget; 4] = [
+        PauseTarget::Minting,
+        PauseTarget::Transfers,
+        PauseTarget::Approvals,
+        PauseTarget::Marketplace,
+    ];
+}
+
+impl fmt::Display for PauseTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PauseTarget::Minting => "minting",
+            PauseTarget::Transfers => "transfers",
+            PauseTarget::Approvals => "approvals",
+            PauseTarget::Marketplace => "marketplace",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Royalty {
+    /// Address receiving the royalties
+    pub payment_address: String,
+    /// Share of the sale price paid as royalty
+    pub share: Decimal,
+}
+
+/// A single attribute of the token, following the OpenSea metadata standard.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// On-chain metadata of the token, in the same shape as cw721-metadata-onchain.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Metadata {
+    pub image: Option<String>,
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    pub background_color: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -76,24 +514,26 @@ pub struct MintMsg {
 pub enum QueryMsg {
     // The price of the token
     AskingPrice {
-        token_id: u64,
+        token_id: String,
     },
     // Owner of the token
     OwnerOf {
-        token_id: u64,
+        token_id: String,
         include_expired: Option<bool>,
     },
     // Return the operator, who has approval for given token
     Approval {
-        token_id: u64,
+        token_id: String,
         operator: String,
         include_expired: Option<bool>,
     },
 
-    // All approvals for the given token
+    // Approvals for the given token
     Approvals {
-        token_id: u64,
+        token_id: String,
         include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
     // Return all operators with access to all of the given owner's tokens
     AllOperators {
@@ -102,7 +542,37 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
-    // Number of tokens issued thus far
+    // Owners which made operator an operator of all their tokens
+    ApprovedOwners {
+        operator: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Tokens operator has been approved for, one by one
+    ApprovedTokens {
+        operator: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return the current minter, if minting is still open
+    Minter {},
+    // Return the admin and the platform fee
+    Config {},
+    // Return the public mint configuration and how many tokens can still be minted
+    PublicMint {},
+    // Number of tokens the address has minted in the public mint
+    PublicMintCount {
+        address: String,
+    },
+    // Return the allowlist mint configuration
+    Allowlist {},
+    // Number of tokens the address has minted in the allowlist mint
+    AllowlistMintCount {
+        address: String,
+    },
+    // Number of tokens currently in existence, excluding burned tokens
     NumTokens {},
     // Return the contract info.
     // Part of Metadata Extension
@@ -110,19 +580,89 @@ pub enum QueryMsg {
     // Return NFT info.
     // Part of Metadata Extension
     NftInfo {
-        token_id: u64,
+        token_id: String,
     },
     // Return NFT info and OwnerOf response.
     // Part of Metadata Extension
     AllNftInfo {
-        token_id: u64,
+        token_id: String,
         include_expired: Option<bool>,
     },
+    // Royalty to be paid on a sale of the token at sale_price.
+    // Part of CW2981 Extension
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    // Whether this contract implements royalties.
+    // Part of CW2981 Extension
+    CheckRoyalties {},
+    // Return the auction running for the token
+    Auction {
+        token_id: String,
+    },
+    // Return all the auctions which have not been settled yet
+    Auctions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return the Dutch auction the token is listed in
+    DutchAuction {
+        token_id: String,
+    },
+    // Return the bids placed in the auction running for the token, oldest first
+    Bids {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Return the offers made on the token, ordered by bidder
+    Offers {
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return the offers made by the bidder, ordered by token
+    OffersByBidder {
+        bidder: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return the collection offer with the given id
+    CollectionOffer {
+        offer_id: u64,
+    },
+    // Return all the collection offers, ordered by id
+    CollectionOffers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Return the highest collection offer in the given denom which has not expired,
+    // or none if too many expired offers are above it
+    BestCollectionOffer {
+        denom: String,
+    },
+    // Return all the tokens owned by the given address.
+    // Part of Enumerable Extension
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return all the tokens issued by this contract.
+    // Part of Enumerable Extension
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AskingPriceResponse {
     pub price: Vec<Coin>,
+    pub price_mode: PriceMode,
+    pub cw20_price: Option<Cw20CoinVerified>,
+    pub listed: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -139,6 +679,75 @@ pub struct ApprovalResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ApprovalsResponse {
     pub approvals: Vec<Approval>,
+    /// Operator to start after for the next page, if there may be one
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorsResponse {
+    pub operators: Vec<cw721::Approval>,
+    /// Operator to start after for the next page, if there may be one
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+    pub pending_minter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub platform_fee: Option<PlatformFee>,
+    pub max_batch_size: u32,
+    pub paused: Vec<PauseTarget>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PublicMintResponse {
+    pub public_mint: Option<PublicMintConfig>,
+    pub max_supply: Option<u64>,
+    /// Number of tokens minted so far, including burned ones
+    pub minted: u64,
+    /// Proceeds of the public mint which have not been withdrawn yet
+    pub proceeds: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PublicMintCountResponse {
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerApproval {
+    pub owner: String,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovedOwnersResponse {
+    pub owners: Vec<OwnerApproval>,
+    /// Owner to start after for the next page, if there may be one
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenApproval {
+    pub token_id: String,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovedTokensResponse {
+    pub tokens: Vec<TokenApproval>,
+    /// Token to start after for the next page, if there may be one
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistResponse {
+    pub allowlist: Option<AllowlistConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -154,7 +763,9 @@ pub struct ContractInfoResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct NftInfoResponse {
-    pub token_uri: String,
+    pub token_uri: Option<String>,
+    pub extension: Option<Metadata>,
+    pub transferable: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -162,3 +773,44 @@ pub struct AllNftInfoResponse {
     pub owner: OwnerOfResponse,
     pub info: NftInfoResponse,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionsResponse {
+    pub auctions: Vec<Auction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidsResponse {
+    pub bids: Vec<Bid>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<Offer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionOffersResponse {
+    pub offers: Vec<CollectionOffer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BestCollectionOfferResponse {
+    pub offer: Option<CollectionOffer>,
+}
@@ -1,26 +1,48 @@
-use cosmwasm_std::{entry_point, to_binary, Addr, Binary, Deps, Env, Order, StdError, StdResult};
-use cw721::{Expiration, OperatorsResponse};
+use cosmwasm_std::{
+    entry_point, to_binary, Addr, Binary, Coin, Deps, Env, Order, StdError, StdResult, Uint128,
+};
+use cw721::Expiration;
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
 use crate::msg::{
-    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, AskingPriceResponse,
-    ContractInfoResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse, QueryMsg,
+    AllNftInfoResponse, AllowlistResponse, Approval, ApprovalResponse, ApprovalsResponse,
+    ApprovedOwnersResponse, ApprovedTokensResponse, AskingPriceResponse, Auction, AuctionsResponse,
+    BestCollectionOfferResponse, BidsResponse, CheckRoyaltiesResponse, CollectionOffer,
+    CollectionOffersResponse, ConfigResponse, ContractInfoResponse, DutchAuction, MinterResponse,
+    NftInfoResponse, NumTokensResponse, OffersResponse, OperatorsResponse, OwnerApproval,
+    OwnerOfResponse, PriceMode, PublicMintCountResponse, PublicMintResponse, QueryMsg,
+    RoyaltiesInfoResponse, Royalty, TokenApproval, TokensResponse,
+};
+use crate::state::{
+    collection_offers, offers, token_approvals, tokens, State, TokenInfo, ALLOWLIST_MINTS,
+    AUCTIONS, BIDS, CONFIG, CUSTOM_IDS, DUTCH_AUCTIONS, MINT_PROCEEDS, OPERATORS, OPERATOR_OWNERS,
+    PUBLIC_MINTS,
 };
-use crate::state::{State, TokenInfo, CONFIG, OPERATORS, TOKENS};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
+// expired entries skipped at most by a single page of a query
+const MAX_SKIPPED: usize = 50;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::AskingPrice { token_id } => to_binary(&query_asking_price(deps, env, token_id)?),
+        QueryMsg::AskingPrice { token_id } => to_binary(&query_asking_price(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+        )?),
 
         QueryMsg::OwnerOf {
             token_id,
             include_expired,
-        } => to_binary(&query_owner_of(deps, env, token_id, include_expired)?),
+        } => to_binary(&query_owner_of(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            include_expired,
+        )?),
 
         QueryMsg::Approval {
             token_id,
@@ -29,7 +51,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         } => to_binary(&query_approval(
             deps,
             env,
-            token_id,
+            resolve_token_id(deps, &token_id)?,
             operator,
             include_expired,
         )?),
@@ -37,7 +59,44 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Approvals {
             token_id,
             include_expired,
-        } => to_binary(&query_approvals(deps, env, token_id, include_expired)?),
+            start_after,
+            limit,
+        } => to_binary(&query_approvals(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            include_expired,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::ApprovedOwners {
+            operator,
+            include_expired,
+            start_after,
+            limit,
+        } => to_binary(&query_approved_owners(
+            deps,
+            env,
+            operator,
+            include_expired,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::ApprovedTokens {
+            operator,
+            include_expired,
+            start_after,
+            limit,
+        } => to_binary(&query_approved_tokens(
+            deps,
+            env,
+            operator,
+            include_expired,
+            start_after,
+            limit,
+        )?),
 
         QueryMsg::AllOperators {
             owner,
@@ -53,23 +112,148 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         )?),
 
+        QueryMsg::Minter {} => to_binary(&query_minter(deps, env)?),
+
+        QueryMsg::Config {} => to_binary(&query_contract_config(deps, env)?),
+
+        QueryMsg::PublicMint {} => to_binary(&query_public_mint(deps, env)?),
+
+        QueryMsg::PublicMintCount { address } => {
+            to_binary(&query_public_mint_count(deps, env, address)?)
+        }
+
+        QueryMsg::Allowlist {} => to_binary(&query_allowlist(deps, env)?),
+
+        QueryMsg::AllowlistMintCount { address } => {
+            to_binary(&query_allowlist_mint_count(deps, env, address)?)
+        }
+
         QueryMsg::NumTokens {} => to_binary(&query_num_tokens(deps, env)?),
 
         QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps, env)?),
 
-        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(deps, env, token_id)?),
+        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+        )?),
 
         QueryMsg::AllNftInfo {
             token_id,
             include_expired,
-        } => to_binary(&query_all_nft_info(deps, env, token_id, include_expired)?),
+        } => to_binary(&query_all_nft_info(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            include_expired,
+        )?),
+
+        QueryMsg::RoyaltyInfo {
+            token_id,
+            sale_price,
+        } => to_binary(&query_royalty_info(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            sale_price,
+        )?),
+
+        QueryMsg::CheckRoyalties {} => to_binary(&query_check_royalties(deps, env)?),
+
+        QueryMsg::Auction { token_id } => to_binary(&query_auction(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+        )?),
+
+        QueryMsg::Auctions { start_after, limit } => {
+            to_binary(&query_auctions(deps, env, start_after, limit)?)
+        }
+
+        QueryMsg::DutchAuction { token_id } => to_binary(&query_dutch_auction(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+        )?),
+
+        QueryMsg::Bids {
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query_bids(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::Offers {
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query_offers(
+            deps,
+            env,
+            resolve_token_id(deps, &token_id)?,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::OffersByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&query_offers_by_bidder(
+            deps,
+            env,
+            bidder,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::CollectionOffer { offer_id } => {
+            to_binary(&query_collection_offer(deps, env, offer_id)?)
+        }
+
+        QueryMsg::CollectionOffers { start_after, limit } => {
+            to_binary(&query_collection_offers(deps, env, start_after, limit)?)
+        }
+
+        QueryMsg::BestCollectionOffer { denom } => {
+            to_binary(&query_best_collection_offer(deps, env, denom)?)
+        }
+
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_owner_tokens(deps, env, owner, start_after, limit)?),
+
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_binary(&query_all_tokens(deps, env, start_after, limit)?)
+        }
     }
 }
 
-pub fn query_asking_price(deps: Deps, _env: Env, token_id: u64) -> StdResult<AskingPriceResponse> {
+pub fn query_asking_price(deps: Deps, env: Env, token_id: u64) -> StdResult<AskingPriceResponse> {
     let token_info = query_tokens(deps, token_id)?;
+
+    // Price of a Dutch auction depends on the current time
+    if let Some(auction) = DUTCH_AUCTIONS.may_load(deps.storage, token_id)? {
+        return Ok(AskingPriceResponse {
+            price: vec![auction.price_at(env.block.time)],
+            price_mode: PriceMode::AllOf,
+            cw20_price: None,
+            listed: env.block.time >= auction.start_time,
+        });
+    }
+
     Ok(AskingPriceResponse {
         price: token_info.base_price,
+        price_mode: token_info.price_mode,
+        cw20_price: token_info.cw20_price,
+        listed: token_info.listed,
     })
 }
 
@@ -80,13 +264,7 @@ fn query_owner_of(
     include_expired: Option<bool>,
 ) -> StdResult<OwnerOfResponse> {
     let token = query_tokens(deps, token_id)?;
-    let include_expired = include_expired.unwrap_or(false);
-
-    let approvals = token
-        .approvals
-        .into_iter()
-        .filter(|appr| include_expired || !appr.expires.is_expired(&env.block))
-        .collect();
+    let approvals = approvals_of(deps, &env, token_id, include_expired.unwrap_or(false))?;
 
     Ok(OwnerOfResponse {
         owner: token.owner.into_string(),
@@ -101,25 +279,15 @@ fn query_approval(
     operator: String,
     include_expired: Option<bool>,
 ) -> StdResult<ApprovalResponse> {
-    let token = query_tokens(deps, token_id)?;
+    query_tokens(deps, token_id)?;
     let operator_addr = deps.api.addr_validate(operator.as_str())?;
     let include_expired = include_expired.unwrap_or(false);
 
-    let appr: Vec<Approval> = token
-        .approvals
-        .into_iter()
-        .filter(|val| val.operator == operator_addr)
-        .collect();
-
-    if !appr.is_empty() && (include_expired || !appr[0].expires.is_expired(&env.block)) {
-        let res = ApprovalResponse {
-            approval: Approval {
-                operator: operator_addr,
-                expires: appr[0].expires,
-            },
-        };
-
-        return Ok(res);
+    let appr = token_approvals().may_load(deps.storage, (token_id, &operator_addr))?;
+    if let Some(approval) = appr {
+        if include_expired || !approval.expires.is_expired(&env.block) {
+            return Ok(ApprovalResponse { approval });
+        }
     };
     Err(StdError::NotFound {
         kind: String::from("Approval not found for given address"),
@@ -131,17 +299,46 @@ fn query_approvals(
     env: Env,
     token_id: u64,
     include_expired: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
 ) -> StdResult<ApprovalsResponse> {
+    query_tokens(deps, token_id)?;
     let include_expired = include_expired.unwrap_or(false);
-    let token = query_tokens(deps, token_id)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
 
-    let res = token
-        .approvals
-        .into_iter()
-        .filter(|appr| include_expired || !appr.expires.is_expired(&env.block))
-        .collect();
+    let items = token_approvals()
+        .prefix(token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(operator, appr)| (operator.into_string(), appr)));
+    let (approvals, next_cursor) = paginate(items, limit, |appr: &Approval| {
+        include_expired || !appr.expires.is_expired(&env.block)
+    })?;
+
+    Ok(ApprovalsResponse {
+        approvals,
+        next_cursor,
+    })
+}
 
-    Ok(ApprovalsResponse { approvals: res })
+/// Approvals granted on a single token, which are bounded by the per token cap.
+fn approvals_of(
+    deps: Deps,
+    env: &Env,
+    token_id: u64,
+    include_expired: bool,
+) -> StdResult<Vec<Approval>> {
+    token_approvals()
+        .prefix(token_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|res| {
+            res.as_ref().map_or(true, |(_, appr)| {
+                include_expired || !appr.expires.is_expired(&env.block)
+            })
+        })
+        .map(|res| res.map(|(_, appr)| appr))
+        .collect()
 }
 
 fn query_approved_for_all(
@@ -158,27 +355,204 @@ fn query_approved_for_all(
     let start = start_addr.as_ref().map(Bound::exclusive);
     let owner_addr = deps.api.addr_validate(&owner)?;
 
-    let res: StdResult<Vec<_>> = OPERATORS
+    let items = OPERATORS
         .prefix(&owner_addr)
         .range(deps.storage, start, None, Order::Ascending)
-        .filter(|r| include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block))
-        .take(limit)
-        .map(parse_approval)
-        .collect();
-    Ok(OperatorsResponse { operators: res? })
+        .map(|item| item.map(|(operator, expires)| (operator.to_string(), (operator, expires))));
+    let (res, next_cursor) = paginate(items, limit, |(_, expires): &(Addr, Expiration)| {
+        include_expired || !expires.is_expired(&env.block)
+    })?;
+
+    Ok(OperatorsResponse {
+        operators: res.into_iter().map(parse_approval).collect(),
+        next_cursor,
+    })
+}
+
+/// Take up to `limit` of the `items` for which `keep` holds, along with the
+/// cursor of the last item looked at if there may be more of them. At most
+/// `MAX_SKIPPED` items are skipped, so that a page full of expired entries
+/// cannot make the query scan an unbounded part of the storage. In that
+/// case the page may be short, or even empty, while still having a cursor.
+fn paginate<T>(
+    items: impl Iterator<Item = StdResult<(String, T)>>,
+    limit: usize,
+    keep: impl Fn(&T) -> bool,
+) -> StdResult<(Vec<T>, Option<String>)> {
+    let mut items = items.peekable();
+    let mut res = vec![];
+    let mut skipped = 0;
+    let mut cursor = None;
+
+    while res.len() < limit && skipped < MAX_SKIPPED {
+        let (key, item) = match items.next() {
+            Some(val) => val?,
+            None => break,
+        };
+        if keep(&item) {
+            res.push(item);
+        } else {
+            skipped += 1;
+        }
+        cursor = Some(key);
+    }
+
+    match items.peek() {
+        Some(_) => Ok((res, cursor)),
+        None => Ok((res, None)),
+    }
+}
+
+fn query_approved_owners(
+    deps: Deps,
+    env: Env,
+    operator: String,
+    include_expired: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ApprovedOwnersResponse> {
+    let include_expired = include_expired.unwrap_or(false);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+    let operator_addr = deps.api.addr_validate(&operator)?;
+
+    let items = OPERATOR_OWNERS
+        .prefix(&operator_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(owner, expires)| {
+                let owner = owner.into_string();
+                (owner.clone(), OwnerApproval { owner, expires })
+            })
+        });
+    let (owners, next_cursor) = paginate(items, limit, |appr: &OwnerApproval| {
+        include_expired || !appr.expires.is_expired(&env.block)
+    })?;
+
+    Ok(ApprovedOwnersResponse {
+        owners,
+        next_cursor,
+    })
+}
+
+fn query_approved_tokens(
+    deps: Deps,
+    env: Env,
+    operator: String,
+    include_expired: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ApprovedTokensResponse> {
+    let include_expired = include_expired.unwrap_or(false);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    let start_id = start_after
+        .map(|val| resolve_token_id(deps, &val))
+        .transpose()?;
+    let start = start_id.map(|id| Bound::exclusive((id, operator_addr.clone())));
+
+    // the cursor is the external id, which `start_after` resolves back
+    let items = token_approvals()
+        .idx
+        .operator
+        .prefix(operator_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            let ((token_id, _), appr) = item?;
+            let token_id = query_tokens(deps, token_id)?.external_id();
+            Ok((
+                token_id.clone(),
+                TokenApproval {
+                    token_id,
+                    expires: appr.expires,
+                },
+            ))
+        });
+    let (tokens, next_cursor) = paginate(items, limit, |appr: &TokenApproval| {
+        include_expired || !appr.expires.is_expired(&env.block)
+    })?;
+
+    Ok(ApprovedTokensResponse {
+        tokens,
+        next_cursor,
+    })
 }
 
-fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<cw721::Approval> {
-    item.map(|(spender, expires)| cw721::Approval {
-        spender: spender.to_string(),
+fn parse_approval((spender, expires): (Addr, Expiration)) -> cw721::Approval {
+    cw721::Approval {
+        spender: spender.into_string(),
         expires,
+    }
+}
+
+fn query_minter(deps: Deps, _env: Env) -> StdResult<MinterResponse> {
+    let config = query_config(deps)?;
+    Ok(MinterResponse {
+        minter: config.minter.map(Addr::into_string),
+        pending_minter: config.pending_minter.map(Addr::into_string),
+    })
+}
+
+fn query_contract_config(deps: Deps, _env: Env) -> StdResult<ConfigResponse> {
+    let config = query_config(deps)?;
+    Ok(ConfigResponse {
+        admin: config.admin.into_string(),
+        platform_fee: config.platform_fee,
+        max_batch_size: config.max_batch_size,
+        paused: config.paused,
+    })
+}
+
+fn query_public_mint(deps: Deps, _env: Env) -> StdResult<PublicMintResponse> {
+    let config = query_config(deps)?;
+    let proceeds: StdResult<Vec<_>> = MINT_PROCEEDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin::new(amount.u128(), denom)))
+        .collect();
+    Ok(PublicMintResponse {
+        public_mint: config.public_mint,
+        max_supply: config.max_supply,
+        minted: config.next_token_id - 1,
+        proceeds: proceeds?,
+    })
+}
+
+fn query_public_mint_count(
+    deps: Deps,
+    _env: Env,
+    address: String,
+) -> StdResult<PublicMintCountResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let count = PUBLIC_MINTS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(PublicMintCountResponse { count })
+}
+
+fn query_allowlist(deps: Deps, _env: Env) -> StdResult<AllowlistResponse> {
+    let config = query_config(deps)?;
+    Ok(AllowlistResponse {
+        allowlist: config.allowlist,
     })
 }
 
+fn query_allowlist_mint_count(
+    deps: Deps,
+    _env: Env,
+    address: String,
+) -> StdResult<PublicMintCountResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let count = ALLOWLIST_MINTS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(PublicMintCountResponse { count })
+}
+
 fn query_num_tokens(deps: Deps, _env: Env) -> StdResult<NumTokensResponse> {
     let config = query_config(deps)?;
     Ok(NumTokensResponse {
-        tokens: config.num_tokens,
+        tokens: config.token_count,
     })
 }
 
@@ -193,7 +567,9 @@ fn query_contract_info(deps: Deps, _env: Env) -> StdResult<ContractInfoResponse>
 fn query_nft_info(deps: Deps, _env: Env, token_id: u64) -> StdResult<NftInfoResponse> {
     let token = query_tokens(deps, token_id)?;
     let res = NftInfoResponse {
-        token_uri: token.token_uri.unwrap_or_else(|| "None".to_string()),
+        token_uri: token.token_uri,
+        extension: token.extension,
+        transferable: token.transferable,
     };
     Ok(res)
 }
@@ -211,65 +587,322 @@ fn query_all_nft_info(
     Ok(res)
 }
 
-pub fn query_config(deps: Deps) -> StdResult<State> {
-    let res = CONFIG.may_load(deps.storage)?;
-    match res {
-        Some(val) => Ok(val),
-        None => Err(StdError::GenericErr {
-            msg: String::from("Unable to load internal state"),
-        }),
-    }
+fn query_royalty_info(
+    deps: Deps,
+    _env: Env,
+    token_id: u64,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse> {
+    let token = query_tokens(deps, token_id)?;
+    let res = match token_royalty(deps, &token)? {
+        Some(val) => RoyaltiesInfoResponse {
+            address: val.payment_address,
+            royalty_amount: sale_price * val.share,
+        },
+        None => RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        },
+    };
+    Ok(res)
 }
 
-pub fn query_tokens(deps: Deps, token_id: u64) -> StdResult<TokenInfo> {
-    let res = TOKENS.may_load(deps.storage, token_id)?;
-    match res {
-        Some(val) => Ok(val),
-        None => Err(StdError::NotFound {
-            kind: format!("Unable to load token with token_id: {}", token_id),
-        }),
-    }
+fn query_check_royalties(_deps: Deps, _env: Env) -> StdResult<CheckRoyaltiesResponse> {
+    Ok(CheckRoyaltiesResponse {
+        royalty_payments: true,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::contract::{execute, instantiate};
-    use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg};
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary, Coin, Uint128};
+/// Royalty applying to the token: its own royalty if set, the collection
+/// default otherwise.
+pub fn token_royalty(deps: Deps, token: &TokenInfo) -> StdResult<Option<Royalty>> {
+    if token.royalty.is_some() {
+        return Ok(token.royalty.clone());
+    }
+    let config = query_config(deps)?;
+    Ok(config.default_royalty)
+}
 
-    const DENOM: &str = "ubit";
+fn query_auction(deps: Deps, _env: Env, token_id: u64) -> StdResult<Auction> {
+    AUCTIONS.load(deps.storage, token_id)
+}
 
-    fn init_msg(name: String, symbol: String) -> InstantiateMsg {
-        InstantiateMsg { name, symbol }
-    }
+fn query_auctions(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_id = start_after
+        .map(|val| resolve_token_id(deps, &val))
+        .transpose()?;
+    let start = start_id.map(Bound::exclusive);
 
-    fn mint_msg(owner: String) -> MintMsg {
-        MintMsg {
-            owner,
-            token_uri: None,
-            price: coins(1000, &DENOM.to_string()),
-        }
-    }
+    let res: StdResult<Vec<_>> = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, auction)| auction))
+        .collect();
+    Ok(AuctionsResponse { auctions: res? })
+}
 
-    #[test]
-    fn asking_price() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
-        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(res.messages.len(), 0);
+fn query_dutch_auction(deps: Deps, _env: Env, token_id: u64) -> StdResult<DutchAuction> {
+    DUTCH_AUCTIONS.load(deps.storage, token_id)
+}
 
-        let mint_msg = mint_msg("creator".to_string());
-        let msg = ExecuteMsg::Mint(mint_msg);
+fn query_bids(
+    deps: Deps,
+    _env: Env,
+    token_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BidsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let res: StdResult<Vec<_>> = BIDS
+        .prefix(token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, bid)| bid))
+        .collect();
+    Ok(BidsResponse { bids: res? })
+}
+
+fn query_offers(
+    deps: Deps,
+    _env: Env,
+    token_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let res: StdResult<Vec<_>> = offers()
+        .prefix(token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect();
+    Ok(OffersResponse { offers: res? })
+}
+
+fn query_offers_by_bidder(
+    deps: Deps,
+    _env: Env,
+    bidder: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let bidder_addr = deps.api.addr_validate(&bidder)?;
+    let start_id = start_after
+        .map(|val| resolve_token_id(deps, &val))
+        .transpose()?;
+    let start = start_id.map(|id| Bound::exclusive((id, bidder_addr.clone())));
+
+    let res: StdResult<Vec<_>> = offers()
+        .idx
+        .bidder
+        .prefix(bidder_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect();
+    Ok(OffersResponse { offers: res? })
+}
+
+fn query_collection_offer(deps: Deps, _env: Env, offer_id: u64) -> StdResult<CollectionOffer> {
+    collection_offers().load(deps.storage, offer_id)
+}
+
+fn query_collection_offers(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<CollectionOffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let res: StdResult<Vec<_>> = collection_offers()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect();
+    Ok(CollectionOffersResponse { offers: res? })
+}
+
+fn query_best_collection_offer(
+    deps: Deps,
+    env: Env,
+    denom: String,
+) -> StdResult<BestCollectionOfferResponse> {
+    // Walk the price index from the top, skipping at most `MAX_SKIPPED`
+    // offers which have expired
+    let offer = collection_offers()
+        .idx
+        .price
+        .sub_prefix(denom)
+        .range(deps.storage, None, None, Order::Descending)
+        .take(MAX_SKIPPED + 1)
+        .map(|item| item.map(|(_, offer)| offer))
+        .find(|item| match item {
+            Ok(offer) => !offer.expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .transpose()?;
+    Ok(BestCollectionOfferResponse { offer })
+}
+
+fn query_owner_tokens(
+    deps: Deps,
+    _env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_id = start_after
+        .map(|val| resolve_token_id(deps, &val))
+        .transpose()?;
+    let start = start_id.map(Bound::exclusive);
+    let owner_addr = deps.api.addr_validate(&owner)?;
+
+    let res: StdResult<Vec<_>> = tokens()
+        .idx
+        .owner
+        .prefix(owner_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, token)| token.external_id()))
+        .collect();
+    Ok(TokensResponse { tokens: res? })
+}
+
+fn query_all_tokens(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_id = start_after
+        .map(|val| resolve_token_id(deps, &val))
+        .transpose()?;
+    let start = start_id.map(Bound::exclusive);
+
+    let res: StdResult<Vec<_>> = tokens()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, token)| token.external_id()))
+        .collect();
+    Ok(TokensResponse { tokens: res? })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<State> {
+    let res = CONFIG.may_load(deps.storage)?;
+    match res {
+        Some(val) => Ok(val),
+        None => Err(StdError::GenericErr {
+            msg: String::from("Unable to load internal state"),
+        }),
+    }
+}
+
+/// Resolve the `token_id` used in messages to the numeric id the token is
+/// stored under. Numeric ids are used as is, unless the token has been
+/// minted with a custom id, anything else is looked up among the custom ids
+/// given at mint. Queries taking a `start_after` token resolve it here, so
+/// the cursors they hand out have to be external ids as well.
+pub fn resolve_token_id(deps: Deps, token_id: &str) -> StdResult<u64> {
+    let res = match token_id.parse::<u64>() {
+        Ok(val) => match tokens().may_load(deps.storage, val)? {
+            // tokens minted with a custom id are only known by it
+            Some(token) if token.custom_id.is_some() => None,
+            _ => Some(val),
+        },
+        Err(_) => CUSTOM_IDS.may_load(deps.storage, token_id)?,
+    };
+    match res {
+        Some(val) => Ok(val),
+        None => Err(StdError::NotFound {
+            kind: format!("Unable to load token with token_id: {}", token_id),
+        }),
+    }
+}
+
+pub fn query_tokens(deps: Deps, token_id: u64) -> StdResult<TokenInfo> {
+    let res = tokens().may_load(deps.storage, token_id)?;
+    match res {
+        Some(val) => Ok(val),
+        None => Err(StdError::NotFound {
+            kind: format!("Unable to load token with token_id: {}", token_id),
+        }),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute, instantiate};
+    use crate::msg::{
+        AllowlistConfig, DecayCurve, ExecuteMsg, FeeRecipient, InstantiateMsg, Metadata, MintMsg,
+        PlatformFee, PublicMintConfig, Trait,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary, Coin, Decimal};
+    use sha2::{Digest, Sha256};
+
+    const DENOM: &str = "ubit";
+
+    fn init_msg(name: String, symbol: String) -> InstantiateMsg {
+        InstantiateMsg {
+            name,
+            symbol,
+            minter: None,
+            admin: None,
+            max_supply: None,
+        }
+    }
+
+    fn mint_msg(owner: String) -> MintMsg {
+        MintMsg {
+            token_id: None,
+            owner,
+            token_uri: None,
+            price: coins(1000, &DENOM.to_string()),
+            extension: None,
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        }
+    }
+
+    #[test]
+    fn asking_price() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, &DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+
+        let mint_msg = mint_msg("creator".to_string());
+        let msg = ExecuteMsg::Mint(mint_msg);
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
         assert_eq!(0, res.messages.len());
         assert_eq!(4, res.attributes.len());
 
         // Successful query
-        let query_msg = QueryMsg::AskingPrice { token_id: 1 };
+        let query_msg = QueryMsg::AskingPrice {
+            token_id: String::from("1"),
+        };
         let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
         let res: AskingPriceResponse = from_binary(&res).unwrap();
         assert_eq!(
@@ -279,9 +912,27 @@ mod tests {
                 denom: DENOM.to_string()
             }]
         );
+        assert!(res.listed);
+
+        // Delisted token still reports its price
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Delist {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let query_msg = QueryMsg::AskingPrice {
+            token_id: String::from("1"),
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let res: AskingPriceResponse = from_binary(&res).unwrap();
+        assert_eq!(res.price, coins(1000, DENOM.to_string()));
+        assert!(!res.listed);
 
         // Unsuccessful query
-        let query_msg = QueryMsg::AskingPrice { token_id: 2 };
+        let query_msg = QueryMsg::AskingPrice {
+            token_id: String::from("2"),
+        };
         let res = query(deps.as_ref(), env, query_msg).unwrap_err();
         match res {
             StdError::NotFound { .. } => {}
@@ -335,6 +986,16 @@ mod tests {
         // Query # of tokens after minting
         let res = query_num_tokens(deps.as_ref(), env.clone()).unwrap();
         assert_eq!(res.tokens, 1);
+
+        // Query # of tokens after burning
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::Burn {
+            token_id: String::from("1"),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query_num_tokens(deps.as_ref(), env.clone()).unwrap();
+        assert_eq!(res.tokens, 0);
     }
 
     #[test]
@@ -351,13 +1012,57 @@ mod tests {
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // Successful query
-        let msg = QueryMsg::NftInfo { token_id: 1u64 };
+        let msg = QueryMsg::NftInfo {
+            token_id: String::from("1"),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: NftInfoResponse = from_binary(&res).unwrap();
+        assert_eq!(result.token_uri, None);
+        assert_eq!(result.extension, None);
+
+        // * token with on-chain metadata
+        let extension = Metadata {
+            name: Some(String::from("Sword")),
+            description: Some(String::from("A sharp sword")),
+            image: Some(String::from("ipfs://sword.png")),
+            attributes: Some(vec![Trait {
+                display_type: Some(String::from("number")),
+                trait_type: String::from("damage"),
+                value: String::from("12"),
+            }]),
+            ..Metadata::default()
+        };
+        let msg = ExecuteMsg::Mint(MintMsg {
+            token_id: None,
+            owner: String::from("creator"),
+            token_uri: Some(String::from("https://example.com/sword.json")),
+            price: coins(1000, DENOM.to_string()),
+            extension: Some(extension.clone()),
+            royalty: None,
+            cw20_price: None,
+            price_mode: None,
+            transferable: None,
+        });
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = QueryMsg::NftInfo {
+            token_id: String::from("2"),
+        };
         let res = query(deps.as_ref(), env.clone(), msg).unwrap();
         let result: NftInfoResponse = from_binary(&res).unwrap();
-        assert_eq!(result.token_uri, String::from("None"));
+        assert_eq!(
+            result,
+            NftInfoResponse {
+                token_uri: Some(String::from("https://example.com/sword.json")),
+                extension: Some(extension),
+                transferable: true,
+            }
+        );
 
         // Unsuccessful query
-        let msg = QueryMsg::NftInfo { token_id: 2u64 };
+        let msg = QueryMsg::NftInfo {
+            token_id: String::from("3"),
+        };
         let res = query(deps.as_ref(), env.clone(), msg).unwrap_err();
         match res {
             StdError::NotFound { .. } => {}
@@ -379,7 +1084,7 @@ mod tests {
 
         // Successful query
         let msg = QueryMsg::AllNftInfo {
-            token_id: 1u64,
+            token_id: String::from("1"),
             include_expired: Some(true),
         };
         let res = query(deps.as_ref(), env.clone(), msg).unwrap();
@@ -394,7 +1099,9 @@ mod tests {
         assert_eq!(
             result.info,
             NftInfoResponse {
-                token_uri: String::from("None")
+                token_uri: None,
+                extension: None,
+                transferable: true,
             }
         );
     }
@@ -432,7 +1139,7 @@ mod tests {
         // Approve operator
         let msg = ExecuteMsg::Approve {
             operator: "operator".to_string(),
-            token_id: 1,
+            token_id: String::from("1"),
             expires: None,
         };
         let info = mock_info("creator", &coins(0u128, &DENOM.to_string()));
@@ -490,13 +1197,13 @@ mod tests {
         // Approve operator
         let msg = ExecuteMsg::Approve {
             operator: "operator".to_string(),
-            token_id: 1,
+            token_id: String::from("1"),
             expires: None,
         };
         let info = mock_info("creator", &coins(0u128, &DENOM.to_string()));
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let res = query_approvals(deps.as_ref(), env.clone(), 1u64, None).unwrap();
+        let res = query_approvals(deps.as_ref(), env.clone(), 1u64, None, None, None).unwrap();
 
         assert_eq!(
             res,
@@ -504,8 +1211,1019 @@ mod tests {
                 approvals: vec![Approval {
                     operator: Addr::unchecked("operator"),
                     expires: Expiration::Never {}
-                }]
+                }],
+                next_cursor: None,
+            }
+        )
+    }
+
+    #[test]
+    fn approvals_pagination() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg("owner".to_string()));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let owner_info = mock_info("owner", &[]);
+        for i in 0..5 {
+            let msg = ExecuteMsg::Approve {
+                operator: format!("operator{}", i),
+                token_id: String::from("1"),
+                expires: None,
+            };
+            execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap();
+        }
+
+        // Pages of two approvals
+        let msg = QueryMsg::Approvals {
+            token_id: String::from("1"),
+            include_expired: None,
+            start_after: None,
+            limit: Some(2),
+        };
+        let res: ApprovalsResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.approvals.len(), 2);
+        assert_eq!(res.next_cursor, Some(String::from("operator1")));
+
+        let res = query_approvals(
+            deps.as_ref(),
+            env.clone(),
+            1u64,
+            None,
+            res.next_cursor,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(res.approvals[0].operator, Addr::unchecked("operator2"));
+        assert_eq!(res.next_cursor, Some(String::from("operator3")));
+
+        let res = query_approvals(
+            deps.as_ref(),
+            env.clone(),
+            1u64,
+            None,
+            res.next_cursor,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(res.approvals.len(), 1);
+        assert_eq!(res.next_cursor, None);
+
+        // More expired operators than a single page skips
+        let soon = Expiration::AtHeight(env.block.height + 1);
+        for i in 0..MAX_SKIPPED {
+            let msg = ExecuteMsg::ApproveAll {
+                operator: format!("expired{:02}", i),
+                expires: Some(soon),
+            };
+            execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap();
+        }
+        let msg = ExecuteMsg::ApproveAll {
+            operator: String::from("valid"),
+            expires: None,
+        };
+        execute(deps.as_mut(), env.clone(), owner_info, msg).unwrap();
+        env.block.height += 1;
+
+        let msg = QueryMsg::AllOperators {
+            owner: String::from("owner"),
+            include_expired: None,
+            start_after: None,
+            limit: None,
+        };
+        let res: OperatorsResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert!(res.operators.is_empty());
+        assert_eq!(res.next_cursor, Some(format!("expired{}", MAX_SKIPPED - 1)));
+
+        let res = query_approved_for_all(
+            deps.as_ref(),
+            env.clone(),
+            String::from("owner"),
+            None,
+            res.next_cursor,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.operators.len(), 1);
+        assert_eq!(res.operators[0].spender, "valid");
+        assert_eq!(res.next_cursor, None);
+
+        // Expired entries are still returned when asked for
+        let res = query_approved_for_all(
+            deps.as_ref(),
+            env,
+            String::from("owner"),
+            Some(true),
+            None,
+            Some(MAX_LIMIT),
+        )
+        .unwrap();
+        assert_eq!(res.operators.len(), MAX_LIMIT as usize);
+        assert_eq!(res.next_cursor, Some(String::from("expired29")));
+    }
+
+    #[test]
+    fn approved_owners_and_tokens() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for owner in ["alice", "alice", "bob"] {
+            let msg = ExecuteMsg::Mint(mint_msg(owner.to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        // market is an operator of alice's tokens for a while, and of bob's forever
+        let later = Expiration::AtHeight(env.block.height + 10);
+        for (owner, expires) in [("alice", Some(later)), ("bob", None)] {
+            let msg = ExecuteMsg::ApproveAll {
+                operator: String::from("market"),
+                expires,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+        }
+
+        // and is approved on tokens 2 and 3 one by one
+        for (owner, token_id) in [("alice", "2"), ("bob", "3")] {
+            let msg = ExecuteMsg::Approve {
+                operator: String::from("market"),
+                token_id: String::from(token_id),
+                expires: Some(later),
+            };
+            execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+        }
+
+        let msg = QueryMsg::ApprovedOwners {
+            operator: String::from("market"),
+            include_expired: None,
+            start_after: None,
+            limit: None,
+        };
+        let res: ApprovedOwnersResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.owners,
+            vec![
+                OwnerApproval {
+                    owner: String::from("alice"),
+                    expires: later,
+                },
+                OwnerApproval {
+                    owner: String::from("bob"),
+                    expires: Expiration::Never {},
+                },
+            ]
+        );
+
+        // Pagination
+        let res = query_approved_owners(
+            deps.as_ref(),
+            env.clone(),
+            String::from("market"),
+            None,
+            Some(String::from("alice")),
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(res.owners.len(), 1);
+        assert_eq!(res.owners[0].owner, "bob");
+
+        let msg = QueryMsg::ApprovedTokens {
+            operator: String::from("market"),
+            include_expired: None,
+            start_after: Some(String::from("2")),
+            limit: None,
+        };
+        let res: ApprovedTokensResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.tokens,
+            vec![TokenApproval {
+                token_id: String::from("3"),
+                expires: later,
+            }]
+        );
+
+        // Expired entries are left out, unless asked for
+        env.block.height += 10;
+        let res = query_approved_owners(
+            deps.as_ref(),
+            env.clone(),
+            String::from("market"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.owners.len(), 1);
+        let res = query_approved_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("market"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(res.tokens.is_empty());
+        let res = query_approved_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("market"),
+            Some(true),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.tokens.len(), 2);
+
+        // Revoking removes the owner
+        let msg = ExecuteMsg::RevokeAll {
+            operator: String::from("market"),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), msg).unwrap();
+        let res = query_approved_owners(
+            deps.as_ref(),
+            env,
+            String::from("market"),
+            Some(true),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.owners.len(), 1);
+        assert_eq!(res.owners[0].owner, "alice");
+    }
+
+    #[test]
+    fn approved_tokens_with_custom_ids() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for token_id in ["alpha", "beta"] {
+            let mut msg = mint_msg("alice".to_string());
+            msg.token_id = Some(String::from(token_id));
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::Mint(msg),
+            )
+            .unwrap();
+
+            let msg = ExecuteMsg::Approve {
+                operator: String::from("market"),
+                token_id: String::from(token_id),
+                expires: None,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
+        }
+
+        // The cursor of each page is accepted as the start of the next one
+        let mut start_after = None;
+        let mut pages = vec![];
+        loop {
+            let res = query_approved_tokens(
+                deps.as_ref(),
+                env.clone(),
+                String::from("market"),
+                None,
+                start_after,
+                Some(1),
+            )
+            .unwrap();
+            pages.extend(res.tokens.into_iter().map(|appr| appr.token_id));
+            start_after = res.next_cursor;
+            if start_after.is_none() {
+                break;
             }
+        }
+        assert_eq!(pages, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn owner_tokens() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // Mint tokens 1, 3 and 4 for owner1 and token 2 for owner2
+        for owner in ["owner1", "owner2", "owner1", "owner1"] {
+            let msg = ExecuteMsg::Mint(mint_msg(owner.to_string()));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let msg = QueryMsg::Tokens {
+            owner: String::from("owner1"),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: TokensResponse = from_binary(&res).unwrap();
+        assert_eq!(result.tokens, vec!["1", "3", "4"]);
+
+        // Paginate
+        let res = query_owner_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("owner1"),
+            None,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(res.tokens, vec!["1", "3"]);
+
+        let res = query_owner_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("owner1"),
+            Some(String::from("3")),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(res.tokens, vec!["4"]);
+
+        // Index follows the owner on transfer
+        let info = mock_info("owner1", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::TransferNft {
+            recipient: String::from("owner2"),
+            token_id: String::from("3"),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query_owner_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("owner1"),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.tokens, vec!["1", "4"]);
+
+        let res = query_owner_tokens(
+            deps.as_ref(),
+            env.clone(),
+            String::from("owner2"),
+            None,
+            None,
         )
+        .unwrap();
+        assert_eq!(res.tokens, vec!["2", "3"]);
+
+        // Unknown owner holds no tokens
+        let res =
+            query_owner_tokens(deps.as_ref(), env, String::from("unknown"), None, None).unwrap();
+        assert_eq!(res.tokens, Vec::<String>::new());
+    }
+
+    #[test]
+    fn all_tokens() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for i in 0..12 {
+            let msg = ExecuteMsg::Mint(mint_msg(format!("owner{}", i % 3)));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        // Default limit
+        let msg = QueryMsg::AllTokens {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: TokensResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            result.tokens,
+            (1..=10).map(|val: u64| val.to_string()).collect::<Vec<_>>()
+        );
+
+        // Next page
+        let res =
+            query_all_tokens(deps.as_ref(), env.clone(), Some(String::from("10")), None).unwrap();
+        assert_eq!(res.tokens, vec!["11", "12"]);
+
+        // Limit is capped at MAX_LIMIT
+        let res = query_all_tokens(deps.as_ref(), env.clone(), None, Some(100)).unwrap();
+        assert_eq!(res.tokens.len(), 12);
+
+        // Custom ids are listed in place of the numeric id
+        let mut msg = mint_msg(String::from("owner0"));
+        msg.token_id = Some(String::from("sword"));
+        execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Mint(msg)).unwrap();
+
+        let res =
+            query_all_tokens(deps.as_ref(), env.clone(), Some(String::from("11")), None).unwrap();
+        assert_eq!(res.tokens, vec!["12", "sword"]);
+
+        let res = query_all_tokens(deps.as_ref(), env, Some(String::from("sword")), None).unwrap();
+        assert_eq!(res.tokens, Vec::<String>::new());
+    }
+
+    #[test]
+    fn minter() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = InstantiateMsg {
+            name: String::from("TestNFT"),
+            symbol: String::from("NFT"),
+            minter: Some(String::from("minter")),
+            admin: None,
+            max_supply: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = QueryMsg::Minter {};
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: MinterResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            result,
+            MinterResponse {
+                minter: Some(String::from("minter")),
+                pending_minter: None,
+            }
+        );
+
+        // Pending minter is reported until accepted
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = ExecuteMsg::ProposeMinter {
+            new_minter: String::from("new_minter"),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query_minter(deps.as_ref(), env).unwrap();
+        assert_eq!(res.minter, Some(String::from("minter")));
+        assert_eq!(res.pending_minter, Some(String::from("new_minter")));
+    }
+
+    #[test]
+    fn royalty_info() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg(String::from("creator")));
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::CheckRoyalties {};
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: CheckRoyaltiesResponse = from_binary(&res).unwrap();
+        assert!(result.royalty_payments);
+
+        // No royalty set
+        let msg = QueryMsg::RoyaltyInfo {
+            token_id: String::from("1"),
+            sale_price: Uint128::new(1000),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: RoyaltiesInfoResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            result,
+            RoyaltiesInfoResponse {
+                address: String::new(),
+                royalty_amount: Uint128::zero(),
+            }
+        );
+
+        // Collection default
+        let msg = ExecuteMsg::SetRoyalty {
+            token_id: None,
+            royalty: Some(Royalty {
+                payment_address: String::from("artist"),
+                share: Decimal::percent(10),
+            }),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query_royalty_info(deps.as_ref(), env.clone(), 1, Uint128::new(1000)).unwrap();
+        assert_eq!(
+            res,
+            RoyaltiesInfoResponse {
+                address: String::from("artist"),
+                royalty_amount: Uint128::new(100),
+            }
+        );
+
+        // Unsuccessful query
+        let res = query_royalty_info(deps.as_ref(), env, 2, Uint128::new(1000)).unwrap_err();
+        match res {
+            StdError::NotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn auctions_and_bids() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Mint(mint_msg(String::from("creator")));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let info = mock_info("creator", &[]);
+        for token_id in ["1", "2"] {
+            let msg = ExecuteMsg::StartAuction {
+                token_id: String::from(token_id),
+                reserve_price: Coin::new(100, DENOM),
+                min_increment: Uint128::new(10),
+                ends_at: Expiration::AtHeight(env.block.height + 10),
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        for (bidder, amount) in [("bidder1", 100u128), ("bidder2", 110), ("bidder1", 150)] {
+            let info = mock_info(bidder, &coins(amount, DENOM.to_string()));
+            let msg = ExecuteMsg::PlaceBid {
+                token_id: String::from("2"),
+            };
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+
+        // Single auction
+        let msg = QueryMsg::Auction {
+            token_id: String::from("2"),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: Auction = from_binary(&res).unwrap();
+        assert_eq!(result.token_id, "2");
+        assert_eq!(result.seller, Addr::unchecked("creator"));
+        assert_eq!(result.bid_count, 3);
+        assert_eq!(
+            result.highest_bid.unwrap().amount,
+            Coin::new(150, DENOM.to_string())
+        );
+
+        // All auctions
+        let msg = QueryMsg::Auctions {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: AuctionsResponse = from_binary(&res).unwrap();
+        assert_eq!(result.auctions.len(), 2);
+
+        let res =
+            query_auctions(deps.as_ref(), env.clone(), Some(String::from("1")), None).unwrap();
+        assert_eq!(res.auctions.len(), 1);
+        assert_eq!(res.auctions[0].token_id, "2");
+
+        // Bid history
+        let msg = QueryMsg::Bids {
+            token_id: String::from("2"),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: BidsResponse = from_binary(&res).unwrap();
+        let bidders: Vec<_> = result.bids.iter().map(|bid| bid.bidder.as_str()).collect();
+        assert_eq!(bidders, vec!["bidder1", "bidder2", "bidder1"]);
+
+        let res = query_bids(deps.as_ref(), env.clone(), 2, Some(0), Some(1)).unwrap();
+        assert_eq!(res.bids.len(), 1);
+        assert_eq!(res.bids[0].amount, Coin::new(110, DENOM.to_string()));
+
+        // Unsuccessful query
+        let res = query_auction(deps.as_ref(), env, 3).unwrap_err();
+        match res {
+            StdError::NotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn dutch_auction() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::Mint(mint_msg(String::from("creator")));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let start_time = env.block.time.plus_seconds(10);
+        let msg = ExecuteMsg::StartDutchAuction {
+            token_id: String::from("1"),
+            start_price: Coin::new(300, DENOM),
+            floor_price: Uint128::new(100),
+            start_time,
+            end_time: start_time.plus_seconds(100),
+            curve: DecayCurve::Linear,
+        };
+        let info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Listing
+        let msg = QueryMsg::DutchAuction {
+            token_id: String::from("1"),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: DutchAuction = from_binary(&res).unwrap();
+        assert_eq!(result.token_id, "1");
+        assert_eq!(result.curve, DecayCurve::Linear);
+
+        // Asking price before the start
+        let res = query_asking_price(deps.as_ref(), env.clone(), 1).unwrap();
+        assert_eq!(res.price, vec![Coin::new(300, DENOM)]);
+        assert!(!res.listed);
+
+        // Asking price follows the decay
+        env.block.time = start_time.plus_seconds(25);
+        let res = query_asking_price(deps.as_ref(), env.clone(), 1).unwrap();
+        assert_eq!(res.price, vec![Coin::new(250, DENOM)]);
+        assert!(res.listed);
+
+        // Unsuccessful query
+        let res = query_dutch_auction(deps.as_ref(), env, 2).unwrap_err();
+        match res {
+            StdError::NotFound { .. } => {}
+            e => panic!("{:?}", e),
+        };
+    }
+
+    #[test]
+    fn offers() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for _ in 0..3 {
+            let msg = ExecuteMsg::Mint(mint_msg(String::from("creator")));
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        for (bidder, token_id) in [("bidder1", "1"), ("bidder2", "1"), ("bidder1", "3")] {
+            let info = mock_info(bidder, &coins(100u128, DENOM.to_string()));
+            let msg = ExecuteMsg::MakeOffer {
+                token_id: String::from(token_id),
+                expires: None,
+            };
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+
+        // Offers per token
+        let msg = QueryMsg::Offers {
+            token_id: String::from("1"),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: OffersResponse = from_binary(&res).unwrap();
+        let bidders: Vec<_> = result.offers.iter().map(|o| o.bidder.as_str()).collect();
+        assert_eq!(bidders, vec!["bidder1", "bidder2"]);
+
+        let res = query_offers(
+            deps.as_ref(),
+            env.clone(),
+            1,
+            Some(String::from("bidder1")),
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.offers.len(), 1);
+        assert_eq!(res.offers[0].bidder, Addr::unchecked("bidder2"));
+
+        // Offers per bidder
+        let msg = QueryMsg::OffersByBidder {
+            bidder: String::from("bidder1"),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: OffersResponse = from_binary(&res).unwrap();
+        let token_ids: Vec<_> = result.offers.iter().map(|o| o.token_id.as_str()).collect();
+        assert_eq!(token_ids, vec!["1", "3"]);
+
+        let res = query_offers_by_bidder(
+            deps.as_ref(),
+            env.clone(),
+            String::from("bidder1"),
+            Some(String::from("1")),
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(res.offers.len(), 1);
+        assert_eq!(res.offers[0].token_id, "3");
+
+        let res = query_offers_by_bidder(deps.as_ref(), env, String::from("bidder3"), None, None)
+            .unwrap();
+        assert!(res.offers.is_empty());
+    }
+
+    #[test]
+    fn best_collection_offer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &coins(0u128, DENOM.to_string()));
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let offers = [
+            ("bidder1", 300u128, None),
+            (
+                "bidder2",
+                500,
+                Some(Expiration::AtHeight(env.block.height + 1)),
+            ),
+            ("bidder3", 400, None),
+        ];
+        for (bidder, price, expires) in offers {
+            let info = mock_info(bidder, &coins(price, DENOM.to_string()));
+            let msg = ExecuteMsg::MakeCollectionOffer {
+                price: Coin::new(price, DENOM),
+                quantity: 1,
+                expires,
+            };
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+
+        // Single offer
+        let msg = QueryMsg::CollectionOffer { offer_id: 2 };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: CollectionOffer = from_binary(&res).unwrap();
+        assert_eq!(result.bidder, Addr::unchecked("bidder2"));
+
+        // All offers
+        let msg = QueryMsg::CollectionOffers {
+            start_after: Some(1),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: CollectionOffersResponse = from_binary(&res).unwrap();
+        let ids: Vec<_> = result.offers.iter().map(|o| o.offer_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        // Best offer
+        let msg = QueryMsg::BestCollectionOffer {
+            denom: String::from(DENOM),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg.clone()).unwrap();
+        let result: BestCollectionOfferResponse = from_binary(&res).unwrap();
+        assert_eq!(result.offer.unwrap().offer_id, 2);
+
+        // * expired offers are skipped
+        let mut later = env.clone();
+        later.block.height += 1;
+        let res = query(deps.as_ref(), later.clone(), msg.clone()).unwrap();
+        let result: BestCollectionOfferResponse = from_binary(&res).unwrap();
+        assert_eq!(result.offer.unwrap().offer_id, 3);
+
+        // * no offers in the denom
+        let res =
+            query_best_collection_offer(deps.as_ref(), env.clone(), String::from("uatom")).unwrap();
+        assert_eq!(res.offer, None);
+
+        // * no more than `MAX_SKIPPED` expired offers are skipped
+        for i in 1..MAX_SKIPPED as u128 {
+            let info = mock_info("bidder4", &coins(1000 + i, DENOM.to_string()));
+            let msg = ExecuteMsg::MakeCollectionOffer {
+                price: Coin::new(1000 + i, DENOM),
+                quantity: 1,
+                expires: Some(Expiration::AtHeight(env.block.height + 1)),
+            };
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+        let res = query(deps.as_ref(), later.clone(), msg.clone()).unwrap();
+        let result: BestCollectionOfferResponse = from_binary(&res).unwrap();
+        assert_eq!(result.offer.unwrap().offer_id, 3);
+
+        let info = mock_info("bidder4", &coins(2000u128, DENOM.to_string()));
+        let make_msg = ExecuteMsg::MakeCollectionOffer {
+            price: Coin::new(2000, DENOM),
+            quantity: 1,
+            expires: Some(Expiration::AtHeight(env.block.height + 1)),
+        };
+        execute(deps.as_mut(), env, info, make_msg).unwrap();
+        let res = query(deps.as_ref(), later, msg).unwrap();
+        let result: BestCollectionOfferResponse = from_binary(&res).unwrap();
+        assert_eq!(result.offer, None);
+    }
+
+    #[test]
+    fn config() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &coins(0u128, DENOM.to_string()));
+        let msg = InstantiateMsg {
+            name: String::from("TestNFT"),
+            symbol: String::from("NFT"),
+            minter: None,
+            admin: Some(String::from("admin")),
+            max_supply: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let result: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(result.admin, "admin");
+        assert_eq!(result.platform_fee, None);
+        assert_eq!(result.max_batch_size, 50);
+        assert!(result.paused.is_empty());
+
+        let fee = PlatformFee {
+            bps: 100,
+            recipients: vec![FeeRecipient {
+                address: String::from("treasury"),
+                weight: 1,
+            }],
+        };
+        let msg = ExecuteMsg::UpdatePlatformFee {
+            platform_fee: Some(fee.clone()),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let res = query_contract_config(deps.as_ref(), env).unwrap();
+        assert_eq!(res.platform_fee, Some(fee));
+    }
+
+    #[test]
+    fn public_mint() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+        let mut msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        msg.max_supply = Some(10);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let config = PublicMintConfig {
+            price: coins(100, DENOM),
+            cw20_price: None,
+            per_address_limit: 5,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(10),
+            base_token_uri: None,
+        };
+        let msg = ExecuteMsg::UpdatePublicMint {
+            public_mint: Some(config.clone()),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        for _ in 0..2 {
+            let info = mock_info("alice", &coins(100, DENOM));
+            execute(deps.as_mut(), env.clone(), info, ExecuteMsg::PublicMint {}).unwrap();
+        }
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::PublicMint {}).unwrap();
+        let result: PublicMintResponse = from_binary(&res).unwrap();
+        assert_eq!(result.public_mint, Some(config));
+        assert_eq!(result.max_supply, Some(10));
+        assert_eq!(result.minted, 2);
+        assert_eq!(result.proceeds, coins(200, DENOM));
+
+        let msg = QueryMsg::PublicMintCount {
+            address: String::from("alice"),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: PublicMintCountResponse = from_binary(&res).unwrap();
+        assert_eq!(result.count, 2);
+
+        let res = query_public_mint_count(deps.as_ref(), env, String::from("bob")).unwrap();
+        assert_eq!(res.count, 0);
+    }
+
+    #[test]
+    fn allowlist() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Allowlist {}).unwrap();
+        let result: AllowlistResponse = from_binary(&res).unwrap();
+        assert_eq!(result.allowlist, None);
+
+        // Single leaf tree, the root is the leaf itself
+        let root: [u8; 32] = Sha256::digest(b"alice:1").into();
+        let config = AllowlistConfig {
+            merkle_root: hex::encode(root),
+            price: coins(10, DENOM),
+            cw20_price: None,
+            per_address_limit: 1,
+            start_time: env.block.time,
+            end_time: env.block.time.plus_seconds(10),
+            base_token_uri: None,
+        };
+        let msg = ExecuteMsg::UpdateAllowlist {
+            allowlist: Some(config.clone()),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::MintAllowlisted {
+            proof: vec![],
+            allocation: 1,
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(10, DENOM)),
+            msg,
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Allowlist {}).unwrap();
+        let result: AllowlistResponse = from_binary(&res).unwrap();
+        assert_eq!(result.allowlist, Some(config));
+
+        let msg = QueryMsg::AllowlistMintCount {
+            address: String::from("alice"),
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let result: PublicMintCountResponse = from_binary(&res).unwrap();
+        assert_eq!(result.count, 1);
+
+        let res = query_allowlist_mint_count(deps.as_ref(), env, String::from("bob")).unwrap();
+        assert_eq!(res.count, 0);
+    }
+
+    #[test]
+    fn custom_id_cursors() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("minter", &[]);
+        let msg = init_msg("TestNFT".to_string(), "NFT".to_string());
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        for token_id in ["alpha", "beta"] {
+            let mut msg = mint_msg("alice".to_string());
+            msg.token_id = Some(String::from(token_id));
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::Mint(msg),
+            )
+            .unwrap();
+
+            let msg = ExecuteMsg::MakeOffer {
+                token_id: String::from(token_id),
+                expires: None,
+            };
+            let info = mock_info("bob", &coins(100, DENOM));
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+            let msg = ExecuteMsg::StartAuction {
+                token_id: String::from(token_id),
+                reserve_price: Coin::new(100, DENOM),
+                min_increment: Uint128::new(10),
+                ends_at: Expiration::AtHeight(env.block.height + 10),
+            };
+            execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), msg).unwrap();
+        }
+
+        // Every query hands out external ids, which are accepted as cursors
+        let res = query_all_tokens(deps.as_ref(), env.clone(), None, Some(1)).unwrap();
+        assert_eq!(res.tokens, vec!["alpha"]);
+        let start_after = res.tokens.last().cloned();
+        let res = query_all_tokens(deps.as_ref(), env.clone(), start_after, None).unwrap();
+        assert_eq!(res.tokens, vec!["beta"]);
+
+        let owner = String::from("alice");
+        let res =
+            query_owner_tokens(deps.as_ref(), env.clone(), owner.clone(), None, Some(1)).unwrap();
+        let start_after = res.tokens.last().cloned();
+        let res = query_owner_tokens(deps.as_ref(), env.clone(), owner, start_after, None).unwrap();
+        assert_eq!(res.tokens, vec!["beta"]);
+
+        let res = query_auctions(deps.as_ref(), env.clone(), None, Some(1)).unwrap();
+        let start_after = res.auctions.last().map(|auction| auction.token_id.clone());
+        let res = query_auctions(deps.as_ref(), env.clone(), start_after, None).unwrap();
+        assert_eq!(res.auctions.len(), 1);
+        assert_eq!(res.auctions[0].token_id, "beta");
+
+        let bidder = String::from("bob");
+        let res = query_offers_by_bidder(deps.as_ref(), env.clone(), bidder.clone(), None, Some(1))
+            .unwrap();
+        let start_after = res.offers.last().map(|offer| offer.token_id.clone());
+        let res = query_offers_by_bidder(deps.as_ref(), env, bidder, start_after, None).unwrap();
+        assert_eq!(res.offers.len(), 1);
+        assert_eq!(res.offers[0].token_id, "beta");
     }
 }
@@ -1,35 +1,213 @@
 //! Defines the state and tokeninfo structs
 
-use crate::msg::Approval;
+use crate::msg::{
+    AllowlistConfig, Approval, Auction, Bid, CollectionOffer, DutchAuction, Metadata, Offer,
+    PauseTarget, PlatformFee, PriceMode, PublicMintConfig, Royalty,
+};
+use cw20::Cw20CoinVerified;
 use cw721::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub name: String,
     pub symbol: String,
-    pub minter: Addr,
-    pub num_tokens: u64,
+    /// Address allowed to mint new tokens. `None` once the role has been
+    /// renounced, after which no more tokens can be minted.
+    pub minter: Option<Addr>,
+    /// Address proposed as the next minter, waiting for it to accept
+    pub pending_minter: Option<Addr>,
+    /// Address allowed to change the configuration of the contract
+    pub admin: Addr,
+    /// Id of the next token to be minted. Never decreases, so burned ids
+    /// are not reused.
+    pub next_token_id: u64,
+    /// Number of tokens currently in existence
+    pub token_count: u64,
+    /// Maximum number of tokens which can ever be minted
+    pub max_supply: Option<u64>,
+    /// Public mint phase, open to any address paying the mint price
+    pub public_mint: Option<PublicMintConfig>,
+    /// Allowlist mint phase, open to the addresses committed to its root
+    pub allowlist: Option<AllowlistConfig>,
+    /// Maximum number of items in a single batch message
+    pub max_batch_size: u32,
+    /// Royalty paid on sales of tokens which do not set their own
+    pub default_royalty: Option<Royalty>,
+    /// Id of the next collection offer to be made
+    pub next_collection_offer_id: u64,
+    /// Fee taken by the platform on every sale
+    pub platform_fee: Option<PlatformFee>,
+    /// Groups of actions paused by the admin
+    pub paused: Vec<PauseTarget>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TokenInfo {
     /// Owner of the new token
     pub owner: Addr,
-    /// Approvals to third-party to perform actions on this token
-    pub approvals: Vec<Approval>,
     /// Base price of this token
     pub base_price: Vec<Coin>,
+    /// Whether every coin of `base_price` has to be paid, or any one of them
+    pub price_mode: PriceMode,
+    /// Price in a CW20 token. When set, the token can only be bought by
+    /// sending this amount of the CW20 token, instead of `base_price`.
+    pub cw20_price: Option<Cw20CoinVerified>,
+    /// Whether the token can currently be bought
+    pub listed: bool,
+    /// Soulbound tokens cannot be transferred, approved or sold. They can
+    /// only be burned, by their owner or the minter.
+    pub transferable: bool,
     /// URI of NFT according to ERC 721 Metadata Schema
     pub token_uri: Option<String>,
+    /// Metadata stored on-chain
+    pub extension: Option<Metadata>,
+    /// Royalty paid on sales of this token, overriding the collection default
+    pub royalty: Option<Royalty>,
     /// Unique token_id
     pub token_id: u64,
+    /// Custom id given at mint, used in place of `token_id` in messages
+    pub custom_id: Option<String>,
+}
+
+impl TokenInfo {
+    /// Id of the token as exposed through messages and queries.
+    pub fn external_id(&self) -> String {
+        match &self.custom_id {
+            Some(val) => val.clone(),
+            None => self.token_id.to_string(),
+        }
+    }
 }
 
 pub const CONFIG: Item<State> = Item::new("config");
-pub const TOKENS: Map<u64, TokenInfo> = Map::new("tokens");
 pub const OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("approvals");
+/// Operator side of `OPERATORS`, keyed by (operator, owner)
+pub const OPERATOR_OWNERS: Map<(&Addr, &Addr), Expiration> = Map::new("operator_owners");
+/// Auctions which have not been settled yet, keyed by token
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+/// Bids placed in the running auction of a token, keyed by (token, bid number)
+pub const BIDS: Map<(u64, u64), Bid> = Map::new("bids");
+/// Dutch auction listings, keyed by token
+pub const DUTCH_AUCTIONS: Map<u64, DutchAuction> = Map::new("dutch_auctions");
+/// Number of tokens each address has minted in the public mint
+pub const PUBLIC_MINTS: Map<&Addr, u32> = Map::new("public_mints");
+/// Number of tokens each address has minted in the allowlist mint
+pub const ALLOWLIST_MINTS: Map<&Addr, u32> = Map::new("allowlist_mints");
+/// Mint proceeds which have not been withdrawn, keyed by denom. Kept
+/// apart from the escrowed bids and offers held by the contract.
+pub const MINT_PROCEEDS: Map<&str, Uint128> = Map::new("mint_proceeds");
+/// Mint proceeds paid in CW20 tokens which have not been withdrawn, keyed
+/// by the address of the CW20 contract.
+pub const CW20_MINT_PROCEEDS: Map<&Addr, Uint128> = Map::new("cw20_mint_proceeds");
+/// Custom token ids given at mint, mapped to the numeric id of the token
+pub const CUSTOM_IDS: Map<&str, u64> = Map::new("custom_ids");
+
+/// Secondary indexes on the stored tokens
+pub struct TokenIndexes<'a> {
+    /// Tokens held by an owner
+    pub owner: MultiIndex<'a, Addr, TokenInfo, u64>,
+}
+
+impl<'a> IndexList<TokenInfo> for TokenIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+        let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// All the tokens issued by this contract, keyed by `token_id` and indexed
+/// by owner.
+pub fn tokens<'a>() -> IndexedMap<'a, u64, TokenInfo, TokenIndexes<'a>> {
+    let indexes = TokenIndexes {
+        owner: MultiIndex::new(
+            |token: &TokenInfo| token.owner.clone(),
+            "tokens",
+            "tokens__owner",
+        ),
+    };
+    IndexedMap::new("tokens", indexes)
+}
+
+/// Secondary indexes on the token approvals
+pub struct ApprovalIndexes<'a> {
+    /// Approvals granted to an operator
+    pub operator: MultiIndex<'a, Addr, Approval, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Approval> for ApprovalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Approval>> + '_> {
+        let v: Vec<&dyn Index<Approval>> = vec![&self.operator];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Approvals to third parties to transfer a single token, keyed by
+/// (token, operator) and indexed by operator. The operator is kept in the
+/// value as well, for the index to be built from it.
+pub fn token_approvals<'a>() -> IndexedMap<'a, (u64, &'a Addr), Approval, ApprovalIndexes<'a>> {
+    let indexes = ApprovalIndexes {
+        operator: MultiIndex::new(
+            |appr: &Approval| appr.operator.clone(),
+            "token_approvals",
+            "token_approvals__operator",
+        ),
+    };
+    IndexedMap::new("token_approvals", indexes)
+}
+
+/// Secondary indexes on the stored offers
+pub struct OfferIndexes<'a> {
+    /// Offers made by a bidder
+    pub bidder: MultiIndex<'a, Addr, Offer, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Standing offers on tokens, keyed by (token, bidder) and indexed by
+/// bidder. Offers are not tied to the owner, so they survive transfers.
+pub fn offers<'a>() -> IndexedMap<'a, (u64, &'a Addr), Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        bidder: MultiIndex::new(
+            |offer: &Offer| offer.bidder.clone(),
+            "offers",
+            "offers__bidder",
+        ),
+    };
+    IndexedMap::new("offers", indexes)
+}
+
+/// Secondary indexes on the stored collection offers
+pub struct CollectionOfferIndexes<'a> {
+    /// Offers by (denom, price per token), to find the best offer in a denom
+    pub price: MultiIndex<'a, (String, u128), CollectionOffer, u64>,
+}
+
+impl<'a> IndexList<CollectionOffer> for CollectionOfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CollectionOffer>> + '_> {
+        let v: Vec<&dyn Index<CollectionOffer>> = vec![&self.price];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Offers on any token of the collection, keyed by offer id and indexed by
+/// price.
+pub fn collection_offers<'a>() -> IndexedMap<'a, u64, CollectionOffer, CollectionOfferIndexes<'a>> {
+    let indexes = CollectionOfferIndexes {
+        price: MultiIndex::new(
+            |offer: &CollectionOffer| (offer.price.denom.clone(), offer.price.amount.u128()),
+            "collection_offers",
+            "collection_offers__price",
+        ),
+    };
+    IndexedMap::new("collection_offers", indexes)
+}